@@ -1,17 +1,16 @@
 use ferrux_viewport::error::ViewportError;
 use ferrux_viewport::viewport::{ViewportFactory};
-use rgb::{ComponentBytes, RGBA8, RGBA};
 use winit::event::Event;
 use winit::{event_loop::EventLoop, dpi::LogicalSize, window::WindowBuilder};
 
-// Definition of the colors to use using the `rgb` crate
-static WHITE: [RGBA<u8>; 1] = [RGBA8::new(255, 255, 255, 255)];
-static WHITE_LOW_ALPHA: [RGBA<u8>; 1] = [RGBA8::new(255, 255, 255, 25)];
-static RED: [RGBA<u8>; 1] = [RGBA8::new(255, 0, 0, 255)];
-static YELLOW: [RGBA<u8>; 1] = [RGBA8::new(255, 255, 0, 255)];
-static GREEN: [RGBA<u8>; 1] = [RGBA8::new(0, 255, 0, 255)];
-static CYAN: [RGBA<u8>; 1] = [RGBA8::new(0, 255, 255, 255)];
-static BLUE: [RGBA<u8>; 1] = [RGBA8::new(0, 0, 255, 255)];
+// Definition of the colors to use, any type implementing `Color` is valid
+static WHITE: [u8; 4] = [255, 255, 255, 255];
+static WHITE_LOW_ALPHA: [u8; 4] = [255, 255, 255, 25];
+static RED: [u8; 3] = [255, 0, 0];
+static YELLOW: (u8, u8, u8, u8) = (255, 255, 0, 255);
+static GREEN: u32 = 0x00FF00FF;
+static CYAN: [u8; 4] = [0, 255, 255, 255];
+static BLUE: [u8; 4] = [0, 0, 255, 255];
 
 fn main() -> Result<(), ViewportError> {
 	let event_loop = EventLoop::new();
@@ -51,16 +50,16 @@ fn main() -> Result<(), ViewportError> {
 			let (left, right) = if i > 0.0 { (1.0, i) } else { (i + 1.0, 0.0) }; 
 			let right_y = right / 2.0;  
 			// White line drawing
-			viewport.draw_line((-1.0, 0.25, 0.1), (-1.0 + left, 0.25 - left / 4.0, 0.25), WHITE.as_bytes());
+			viewport.draw_line((-1.0, 0.25, 0.1), (-1.0 + left, 0.25 - left / 4.0, 0.25), WHITE);
 			// Center triangle drawing with fill and border
-			viewport.fill_triangle((0.0, -0.25, 0.0), (-0.25, 0.25, 0.0), (0.25, 0.25, 0.0), WHITE_LOW_ALPHA.as_bytes());
-			viewport.draw_triangle((0.0, -0.25, 0.01), (-0.25, 0.25, 0.01), (0.25, 0.25, 0.01), WHITE.as_bytes());
+			viewport.fill_triangle((0.0, -0.25, 0.0), (-0.25, 0.25, 0.0), (0.25, 0.25, 0.0), WHITE_LOW_ALPHA);
+			viewport.draw_triangle((0.0, -0.25, 0.01), (-0.25, 0.25, 0.01), (0.25, 0.25, 0.01), WHITE);
 			// Colored triangles drawing
-			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.5 * right_y, -0.2), (0.0 + right, -0.3 * right_y, -0.2), RED.as_bytes());
-			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.3 * right_y, -0.2), (0.0 + right, -0.1 * right_y, -0.2), YELLOW.as_bytes());
-			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.1 * right_y, -0.2), (0.0 + right, 0.1 * right_y, -0.2), GREEN.as_bytes());
-			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.1 * right_y, -0.2), (0.0 + right, 0.3 * right_y, -0.2), CYAN.as_bytes());
-			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.3 * right_y, -0.2), (0.0 + right, 0.5 * right_y, -0.2), BLUE.as_bytes());
+			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.5 * right_y, -0.2), (0.0 + right, -0.3 * right_y, -0.2), RED);
+			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.3 * right_y, -0.2), (0.0 + right, -0.1 * right_y, -0.2), YELLOW);
+			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.1 * right_y, -0.2), (0.0 + right, 0.1 * right_y, -0.2), GREEN);
+			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.1 * right_y, -0.2), (0.0 + right, 0.3 * right_y, -0.2), CYAN);
+			viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.3 * right_y, -0.2), (0.0 + right, 0.5 * right_y, -0.2), BLUE);
 			
 			// Invoke render
 			viewport.render().expect("render failed");
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use ferrux_viewport::error::ViewportError;
+use ferrux_viewport::viewport::{HeadlessViewport, ViewportFactory};
+
+// Definition of the colors to use, any type implementing `Color` is valid
+static WHITE: [u8; 4] = [255, 255, 255, 255];
+static WHITE_LOW_ALPHA: [u8; 4] = [255, 255, 255, 25];
+static RED: [u8; 3] = [255, 0, 0];
+static YELLOW: (u8, u8, u8, u8) = (255, 255, 0, 255);
+static GREEN: u32 = 0x00FF00FF;
+static CYAN: [u8; 4] = [0, 255, 255, 255];
+static BLUE: [u8; 4] = [0, 0, 255, 255];
+
+/// Number of frames to save, a full cycle of the figure
+const FRAMES: usize = 80;
+
+fn main() -> Result<(), ViewportError> {
+	let folder = std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("frames"));
+	std::fs::create_dir_all(&folder).map_err(ViewportError::Io)?;
+
+	let mut viewport = ViewportFactory::headless(960, 720, 100)?;
+
+	let mut i = -1.0;
+	let step = 0.05;
+	let mut incrementing = true;
+
+	for frame in 0..FRAMES {
+		i += if incrementing { step } else { -step };
+		if i >= 1.0 {
+			incrementing = false
+		} else if i <= -1.0 {
+			incrementing = true
+		}
+		draw_frame(&mut viewport, i);
+
+		// Invoke render, it only copies the buffer into memory
+		viewport.render()?;
+		viewport.save_png(folder.join(format!("frame_{frame:03}.png")))?;
+		// Clear buffer to let it prepared for the next frame
+		viewport.reset_buffer();
+	}
+	Ok(())
+}
+
+/// Draws the figure in the given step of its morphing, from `-1.0` to `1.0`
+fn draw_frame(viewport: &mut HeadlessViewport<u32>, i: f32) {
+	let (left, right) = if i > 0.0 { (1.0, i) } else { (i + 1.0, 0.0) };
+	let right_y = right / 2.0;
+	// White line drawing
+	viewport.draw_line((-1.0, 0.25, 0.1), (-1.0 + left, 0.25 - left / 4.0, 0.25), WHITE);
+	// Center triangle drawing with fill and border
+	viewport.fill_triangle((0.0, -0.25, 0.0), (-0.25, 0.25, 0.0), (0.25, 0.25, 0.0), WHITE_LOW_ALPHA);
+	viewport.draw_triangle((0.0, -0.25, 0.01), (-0.25, 0.25, 0.01), (0.25, 0.25, 0.01), WHITE);
+	// Colored triangles drawing
+	viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.5 * right_y, -0.2), (0.0 + right, -0.3 * right_y, -0.2), RED);
+	viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.3 * right_y, -0.2), (0.0 + right, -0.1 * right_y, -0.2), YELLOW);
+	viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, -0.1 * right_y, -0.2), (0.0 + right, 0.1 * right_y, -0.2), GREEN);
+	viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.1 * right_y, -0.2), (0.0 + right, 0.3 * right_y, -0.2), CYAN);
+	viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + right, 0.3 * right_y, -0.2), (0.0 + right, 0.5 * right_y, -0.2), BLUE);
+}
+
+#[test]
+fn draw_frame_test() {
+	let mut viewport = ViewportFactory::headless(96, 72, 100).unwrap();
+	draw_frame(&mut viewport, 0.5);
+	viewport.render().unwrap();
+
+	// the white line ends at the center, in front of the translucent triangle
+	assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)), Some((WHITE, 62)));
+	let center = (36 * 96 + 48) * 4;
+	assert_eq!(viewport.renderer().frame()[center..center + 4], WHITE);
+	// which covers the colored triangles behind it
+	assert_eq!(viewport.get_pixel((0.0, 0.1, 0.0)), Some((WHITE_LOW_ALPHA, 50)));
+	// and these fan out to the right
+	assert_eq!(viewport.get_pixel((0.4, 0.0, 0.0)), Some((CYAN, 40)));
+}
@@ -0,0 +1,116 @@
+//! Contains the modes to mix the colors drawn with the ones already in the buffer
+
+/// Defines how a drawn color is mixed with the color already in the buffer when it passes the depth test
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+	/// The drawn color replaces the previous one. The alpha will only be mixed with the black background on render.
+	#[default]
+	Replace,
+	/// The drawn color is composited over the previous one using its alpha (source-over).
+	AlphaOver,
+	/// The channels of the drawn color are added to the previous ones, clamped to the maximum. Useful for glows.
+	Additive,
+	/// The channels of the drawn color are multiplied by the previous ones, normalized. Useful for shadows.
+	Multiply,
+}
+
+impl BlendMode {
+	/// Returns the color resulting of drawing the `source` color over the `destination` one
+	pub(crate) fn blend(&self, source: [u8; 4], destination: [u8; 4]) -> [u8; 4] {
+		match self {
+			BlendMode::AlphaOver if source[3] < u8::MAX => alpha_over(source, destination),
+			BlendMode::Additive => combine(source, destination, u8::saturating_add),
+			BlendMode::Multiply => combine(source, destination, |s, d| {
+				((s as u16 * d as u16 + 127) / 255) as u8
+			}),
+			_ => source,
+		}
+	}
+}
+
+/// Composites the `source` color over the `destination` color with the source-over operator
+fn alpha_over(source: [u8; 4], destination: [u8; 4]) -> [u8; 4] {
+	let source_alpha = source[3] as f32 / 255.0;
+	let destination_alpha = destination[3] as f32 / 255.0 * (1.0 - source_alpha);
+	let alpha = source_alpha + destination_alpha;
+	if alpha == 0.0 {
+		return [0, 0, 0, 0];
+	}
+	let mut color = [0; 4];
+	for i in 0..3 {
+		let channel =
+			(source[i] as f32 * source_alpha + destination[i] as f32 * destination_alpha) / alpha;
+		color[i] = channel.round() as u8;
+	}
+	color[3] = (alpha * 255.0).round() as u8;
+	color
+}
+
+/// Combines each channel of the `source` color with the same channel of the `destination` color
+fn combine(source: [u8; 4], destination: [u8; 4], operation: impl Fn(u8, u8) -> u8) -> [u8; 4] {
+	let mut color = [0; 4];
+	for i in 0..4 {
+		color[i] = operation(source[i], destination[i]);
+	}
+	color
+}
+
+#[test]
+fn replace_test() {
+	assert_eq!(
+		BlendMode::Replace.blend([255, 0, 0, 128], [0, 0, 255, 255]),
+		[255, 0, 0, 128]
+	);
+}
+
+#[test]
+fn alpha_over_test() {
+	let blend = BlendMode::AlphaOver;
+	assert_eq!(
+		blend.blend([255, 0, 0, 128], [0, 0, 255, 255]),
+		[128, 0, 127, 255]
+	);
+	assert_eq!(
+		blend.blend([255, 0, 0, 255], [0, 0, 255, 255]),
+		[255, 0, 0, 255]
+	);
+	assert_eq!(
+		blend.blend([255, 0, 0, 0], [0, 0, 255, 255]),
+		[0, 0, 255, 255]
+	);
+	assert_eq!(
+		blend.blend([255, 0, 0, 128], [0, 0, 0, 0]),
+		[255, 0, 0, 128]
+	);
+	assert_eq!(blend.blend([0, 0, 0, 0], [0, 0, 0, 0]), [0, 0, 0, 0]);
+}
+
+#[test]
+fn additive_test() {
+	let blend = BlendMode::Additive;
+	assert_eq!(
+		blend.blend([128, 0, 0, 255], [128, 0, 0, 255]),
+		[255, 0, 0, 255]
+	);
+	assert_eq!(
+		blend.blend([100, 0, 0, 255], [100, 50, 0, 255]),
+		[200, 50, 0, 255]
+	);
+	assert_eq!(
+		blend.blend([0, 0, 0, 0], [10, 20, 30, 40]),
+		[10, 20, 30, 40]
+	);
+}
+
+#[test]
+fn multiply_test() {
+	let blend = BlendMode::Multiply;
+	assert_eq!(
+		blend.blend([255, 255, 255, 255], [10, 20, 30, 40]),
+		[10, 20, 30, 40]
+	);
+	assert_eq!(
+		blend.blend([128, 0, 255, 255], [255, 255, 128, 255]),
+		[128, 0, 128, 255]
+	);
+}
@@ -0,0 +1,122 @@
+//! Contains a perspective camera to project world coordinates into the normalized space of the viewport
+
+use crate::Position;
+
+/// Perspective camera placed in the world looking at a target. It converts world coordinates into the
+/// normalized coordinates expected by the drawing functions of the [`Viewport`](crate::viewport::Viewport).
+///
+/// The projected positions follow the axes of the viewport:
+/// * `x`: west -> east
+/// * `y`: north -> south, so the world up is the top of the screen
+/// * `z`: far -> near, being `1.0` the near plane and `-1.0` the far plane
+///
+/// # Example
+/// ```
+/// use ferrux_viewport::camera::Camera;
+///
+/// let camera = Camera::new((0.0, 0.0, 5.0), (0.0, 0.0, 0.0), std::f32::consts::FRAC_PI_2, 1.0, 100.0);
+/// let (x, y, _) = camera.project((0.0, 0.0, 0.0));
+/// assert_eq!((x, y), (0.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+	/// Position of the camera in the world
+	pub position: (f32, f32, f32),
+	/// Point of the world the camera is looking at
+	pub target: (f32, f32, f32),
+	/// Direction of the world considered up
+	pub up: (f32, f32, f32),
+	/// Vertical field of view, in radians
+	pub fov: f32,
+	/// Ratio between the width and the height of the screen
+	pub aspect: f32,
+	/// Distance to the near clipping plane
+	pub near: f32,
+	/// Distance to the far clipping plane
+	pub far: f32,
+}
+
+impl Camera {
+	/// Builds a new camera with the `y` axis as up and a square aspect ratio.
+	///
+	/// # Arguments
+	/// * `position`, position of the camera in the world.
+	/// * `target`, point of the world to look at.
+	/// * `fov`, vertical field of view, in radians.
+	/// * `near`, distance to the near clipping plane, must be positive.
+	/// * `far`, distance to the far clipping plane, must be greater than `near`.
+	pub fn new(position: (f32, f32, f32), target: (f32, f32, f32), fov: f32, near: f32, far: f32) -> Self {
+		Self {
+			position,
+			target,
+			up: (0.0, 1.0, 0.0),
+			fov,
+			aspect: 1.0,
+			near,
+			far,
+		}
+	}
+
+	/// Projects the world coordinates into the normalized coordinates of the viewport.
+	/// The points behind the camera or out of the field of view fall out of the `[-1.0, 1.0]` range.
+	///
+	/// # Arguments
+	/// * `world`, coordinates of the point in the world.
+	pub fn project(&self, world: (f32, f32, f32)) -> Position<f32> {
+		let forward = normalize(sub(self.target, self.position));
+		let right = normalize(cross(forward, self.up));
+		let up = cross(right, forward);
+
+		let relative = sub(world, self.position);
+		let (x, y, z) = (dot(relative, right), dot(relative, up), dot(relative, forward));
+
+		let scale = 1.0 / (self.fov * 0.5).tan();
+		let depth = (self.far + self.near) / (self.far - self.near) - 2.0 * self.far * self.near / ((self.far - self.near) * z);
+		(x * scale / (self.aspect * z), -y * scale / z, -depth)
+	}
+}
+
+fn sub(a: (f32, f32, f32), b: (f32, f32, f32)) -> (f32, f32, f32) {
+	(a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn dot(a: (f32, f32, f32), b: (f32, f32, f32)) -> f32 {
+	a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+fn cross(a: (f32, f32, f32), b: (f32, f32, f32)) -> (f32, f32, f32) {
+	(a.1 * b.2 - a.2 * b.1, a.2 * b.0 - a.0 * b.2, a.0 * b.1 - a.1 * b.0)
+}
+
+fn normalize(a: (f32, f32, f32)) -> (f32, f32, f32) {
+	let length = dot(a, a).sqrt();
+	(a.0 / length, a.1 / length, a.2 / length)
+}
+
+#[cfg(test)]
+fn assert_close((ax, ay, az): Position<f32>, (bx, by, bz): Position<f32>) {
+	assert!((ax - bx).abs() < 1e-5 && (ay - by).abs() < 1e-5 && (az - bz).abs() < 1e-5, "{:?} != {:?}", (ax, ay, az), (bx, by, bz));
+}
+
+#[test]
+fn optical_axis_test() {
+	let camera = Camera::new((1.0, 2.0, 3.0), (4.0, 6.0, 3.0), std::f32::consts::FRAC_PI_2, 1.0, 10.0);
+	let (x, y, _) = camera.project((2.5, 4.0, 3.0));
+	assert_close((x, y, 0.0), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn depth_test() {
+	let camera = Camera::new((0.0, 0.0, 0.0), (0.0, 0.0, -1.0), std::f32::consts::FRAC_PI_2, 1.0, 10.0);
+	assert_close(camera.project((0.0, 0.0, -1.0)), (0.0, 0.0, 1.0));
+	assert_close(camera.project((0.0, 0.0, -10.0)), (0.0, 0.0, -1.0));
+}
+
+#[test]
+fn axes_test() {
+	let mut camera = Camera::new((0.0, 0.0, 0.0), (0.0, 0.0, -1.0), std::f32::consts::FRAC_PI_2, 1.0, 10.0);
+	assert_close(camera.project((2.0, 0.0, -2.0)), (1.0, 0.0, -0.11111111));
+	assert_close(camera.project((0.0, 1.0, -2.0)), (0.0, -0.5, -0.11111111));
+	camera.aspect = 2.0;
+	assert_close(camera.project((2.0, 0.0, -2.0)), (0.5, 0.0, -0.11111111));
+}
@@ -0,0 +1,281 @@
+//! Contains the representations of the colors accepted by the library
+
+use crate::error::ViewportError;
+
+/// Color to draw in the viewport. The library works with RGBA colors, so every color must be
+/// convertible to the array of its four channels.
+///
+/// It's implemented for the following representations:
+/// * `[u8; 4]` and `(u8, u8, u8, u8)`, RGBA colors.
+/// * `[u8; 3]`, RGB colors, they will be fully opaque.
+/// * `[u8; 1]`, grayscale intensities, they will be fully opaque.
+/// * `u32`, RGBA colors packed as `0xRRGGBBAA`.
+/// * `rgb::RGBA8` and `rgb::RGB8`, with the `rgb` feature.
+/// * References to any of them.
+pub trait Color {
+	/// Returns the color as the array of its red, green, blue and alpha channels
+	fn to_rgba(&self) -> [u8; 4];
+}
+
+impl Color for [u8; 4] {
+	fn to_rgba(&self) -> [u8; 4] {
+		*self
+	}
+}
+
+impl Color for [u8; 3] {
+	fn to_rgba(&self) -> [u8; 4] {
+		let [r, g, b] = *self;
+		[r, g, b, u8::MAX]
+	}
+}
+
+impl Color for [u8; 1] {
+	fn to_rgba(&self) -> [u8; 4] {
+		let [i] = *self;
+		[i, i, i, u8::MAX]
+	}
+}
+
+impl Color for (u8, u8, u8, u8) {
+	fn to_rgba(&self) -> [u8; 4] {
+		let (r, g, b, a) = *self;
+		[r, g, b, a]
+	}
+}
+
+impl Color for u32 {
+	fn to_rgba(&self) -> [u8; 4] {
+		self.to_be_bytes()
+	}
+}
+
+#[cfg(feature = "rgb")]
+impl Color for rgb::RGBA8 {
+	fn to_rgba(&self) -> [u8; 4] {
+		[self.r, self.g, self.b, self.a]
+	}
+}
+
+#[cfg(feature = "rgb")]
+impl Color for rgb::RGB8 {
+	fn to_rgba(&self) -> [u8; 4] {
+		[self.r, self.g, self.b].to_rgba()
+	}
+}
+
+impl<C: Color + ?Sized> Color for &C {
+	fn to_rgba(&self) -> [u8; 4] {
+		(**self).to_rgba()
+	}
+}
+
+/// Converts a color of unknown length to RGBA, following the same rules as the [Color] implementations of the arrays.
+/// Use it to draw colors coming from slices, like the bytes of other color crates, without risking a panic.
+///
+/// # Error
+/// If the color doesn't have one, three or four channels.
+///
+/// # Example
+/// ```
+/// # use ferrux_viewport::color::from_slice;
+/// let bytes: &[u8] = &[255, 0, 0];
+/// assert_eq!(from_slice(bytes).unwrap(), [255, 0, 0, 255]);
+/// assert!(from_slice(&bytes[..2]).is_err());
+/// ```
+pub fn from_slice(color: &[u8]) -> Result<[u8; 4], ViewportError> {
+	match *color {
+		[i] => Ok([i].to_rgba()),
+		[r, g, b] => Ok([r, g, b].to_rgba()),
+		[r, g, b, a] => Ok([r, g, b, a]),
+		_ => Err(ViewportError::InvalidColor { len: color.len() }),
+	}
+}
+
+/// Converts a color defined by its hue, saturation and value to RGBA, to draw it with any function.
+///
+/// # Arguments
+/// * `h`, hue in degrees. It wraps around, so `360.0` and `-360.0` are the same as `0.0`.
+/// * `s`, saturation from `0.0` to `1.0`, clamped to that range.
+/// * `v`, value from `0.0` to `1.0`, clamped to that range.
+/// * `a`, alpha of the color.
+///
+/// # Example
+/// ```
+/// # use ferrux_viewport::color::hsv;
+/// assert_eq!(hsv(120.0, 1.0, 1.0, 255), [0, 255, 0, 255]);
+/// assert_eq!(hsv(480.0, 1.0, 1.0, 255), [0, 255, 0, 255]);
+/// ```
+pub fn hsv(h: f32, s: f32, v: f32, a: u8) -> [u8; 4] {
+	let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+	let chroma = v * s;
+	hue_to_rgba(h, chroma, v - chroma, a)
+}
+
+/// Converts a color defined by its hue, saturation and lightness to RGBA, to draw it with any function.
+///
+/// # Arguments
+/// * `h`, hue in degrees. It wraps around, so `360.0` and `-360.0` are the same as `0.0`.
+/// * `s`, saturation from `0.0` to `1.0`, clamped to that range.
+/// * `l`, lightness from `0.0` to `1.0`, clamped to that range.
+/// * `a`, alpha of the color.
+///
+/// # Example
+/// ```
+/// # use ferrux_viewport::color::hsl;
+/// assert_eq!(hsl(0.0, 1.0, 0.5, 255), [255, 0, 0, 255]);
+/// assert_eq!(hsl(0.0, 1.0, 1.0, 255), [255, 255, 255, 255]);
+/// ```
+pub fn hsl(h: f32, s: f32, l: f32, a: u8) -> [u8; 4] {
+	let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+	let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+	hue_to_rgba(h, chroma, l - chroma / 2.0, a)
+}
+
+/// Builds the RGBA color of the given hue with the chroma and the amount of gray to add to every channel
+fn hue_to_rgba(h: f32, chroma: f32, gray: f32, a: u8) -> [u8; 4] {
+	let sector = h.rem_euclid(360.0) / 60.0;
+	let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+	let (r, g, b) = match sector as u8 {
+		0 => (chroma, x, 0.0),
+		1 => (x, chroma, 0.0),
+		2 => (0.0, chroma, x),
+		3 => (0.0, x, chroma),
+		4 => (x, 0.0, chroma),
+		_ => (chroma, 0.0, x),
+	};
+	let channel = |c: f32| ((c + gray) * 255.0).round() as u8;
+	[channel(r), channel(g), channel(b), a]
+}
+
+/// Number of channels of the frames rendered into memory, checked at compile time
+pub(crate) struct Channels<const N: usize>;
+
+impl<const N: usize> Channels<N> {
+	/// Fails the build of any frame that doesn't have one, three or four channels
+	pub(crate) const SUPPORTED: () = assert!(N == 1 || N == 3 || N == 4, "frames must have 1, 3 or 4 channels");
+}
+
+/// Packs the RGBA color into `N` channels: the luma with `1`, RGB with `3` and the color as is with `4`.
+/// Without an alpha channel the color is mixed with the black background, the same as a window does on render.
+///
+/// # Example
+/// ```
+/// # use ferrux_viewport::color::to_channels;
+/// assert_eq!(to_channels::<1>([200, 200, 200, 255]), [200]);
+/// assert_eq!(to_channels::<3>([255, 0, 0, 128]), [128, 0, 0]);
+/// assert_eq!(to_channels::<4>([255, 0, 0, 128]), [255, 0, 0, 128]);
+/// ```
+pub fn to_channels<const N: usize>(color: [u8; 4]) -> [u8; N] {
+	let () = Channels::<N>::SUPPORTED;
+	let [r, g, b, a] = color;
+	let mix = |c: u8| ((c as u16 * a as u16 + 127) / 255) as u32;
+	let mut channels = [0; N];
+	match N {
+		1 => channels[0] = ((299 * mix(r) + 587 * mix(g) + 114 * mix(b) + 500) / 1000) as u8,
+		3 => channels.copy_from_slice(&[mix(r) as u8, mix(g) as u8, mix(b) as u8]),
+		_ => channels.copy_from_slice(&color),
+	}
+	channels
+}
+
+/// Maps the RGB channels of the color through the given lookup tables, keeping the alpha
+pub(crate) fn map_color([r, g, b, a]: [u8; 4], lut: &[[u8; 256]; 3]) -> [u8; 4] {
+	[
+		lut[0][r as usize],
+		lut[1][g as usize],
+		lut[2][b as usize],
+		a,
+	]
+}
+
+#[test]
+fn rgba_test() {
+	assert_eq!([255, 0, 0, 128].to_rgba(), [255, 0, 0, 128]);
+	assert_eq!((255, 0, 0, 128).to_rgba(), [255, 0, 0, 128]);
+}
+
+#[test]
+fn rgb_test() {
+	assert_eq!([255, 0, 0].to_rgba(), [255, 0, 0, 255]);
+}
+
+#[test]
+fn grayscale_test() {
+	assert_eq!([200].to_rgba(), [200, 200, 200, 255]);
+}
+
+#[test]
+fn to_channels_test() {
+	assert_eq!(to_channels::<1>([90, 90, 90, 255]), [90]);
+	assert_eq!(to_channels::<1>([255, 0, 0, 255]), [76]);
+	assert_eq!(to_channels::<1>([255, 255, 255, 0]), [0]);
+	assert_eq!(to_channels::<3>([10, 20, 30, 255]), [10, 20, 30]);
+	assert_eq!(to_channels::<4>([10, 20, 30, 40]), [10, 20, 30, 40]);
+}
+
+#[test]
+fn packed_test() {
+	assert_eq!(0xFF000080u32.to_rgba(), [255, 0, 0, 128]);
+	assert_eq!(0x12345678u32.to_rgba(), [0x12, 0x34, 0x56, 0x78]);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_crate_test() {
+	assert_eq!(rgb::RGBA8::new(255, 0, 0, 128).to_rgba(), [255, 0, 0, 128]);
+	assert_eq!(rgb::RGB8::new(0, 255, 0).to_rgba(), [0, 255, 0, 255]);
+}
+
+#[test]
+fn reference_test() {
+	assert_eq!(<&[u8; 3]>::to_rgba(&&[255, 0, 0]), [255, 0, 0, 255]);
+	assert_eq!(<&&u32>::to_rgba(&&&0xFF000080), [255, 0, 0, 128]);
+}
+
+#[test]
+fn from_slice_test() {
+	assert_eq!(from_slice(&[200]).unwrap(), [200, 200, 200, 255]);
+	assert_eq!(from_slice(&[255, 0, 0]).unwrap(), [255, 0, 0, 255]);
+	assert_eq!(from_slice(&[255, 0, 0, 128]).unwrap(), [255, 0, 0, 128]);
+	assert!(matches!(
+		from_slice(&[255, 0]),
+		Err(ViewportError::InvalidColor { len: 2 })
+	));
+	assert!(matches!(
+		from_slice(&[]),
+		Err(ViewportError::InvalidColor { len: 0 })
+	));
+}
+
+#[test]
+fn hsv_test() {
+	assert_eq!(hsv(0.0, 1.0, 1.0, 255), [255, 0, 0, 255]);
+	assert_eq!(hsv(120.0, 1.0, 1.0, 255), [0, 255, 0, 255]);
+	assert_eq!(hsv(240.0, 1.0, 1.0, 128), [0, 0, 255, 128]);
+	assert_eq!(hsv(60.0, 1.0, 1.0, 255), [255, 255, 0, 255]);
+	assert_eq!(hsv(300.0, 1.0, 0.5, 255), [128, 0, 128, 255]);
+	assert_eq!(hsv(90.0, 0.0, 1.0, 255), [255, 255, 255, 255]);
+	assert_eq!(hsv(360.0, 1.0, 1.0, 255), hsv(0.0, 1.0, 1.0, 255));
+	assert_eq!(hsv(-120.0, 1.0, 1.0, 255), hsv(240.0, 1.0, 1.0, 255));
+	assert_eq!(hsv(0.0, 2.0, -1.0, 255), [0, 0, 0, 255]);
+}
+
+#[test]
+fn hsl_test() {
+	assert_eq!(hsl(0.0, 1.0, 0.5, 255), [255, 0, 0, 255]);
+	assert_eq!(hsl(120.0, 1.0, 0.5, 255), [0, 255, 0, 255]);
+	assert_eq!(hsl(240.0, 1.0, 0.25, 255), [0, 0, 128, 255]);
+	assert_eq!(hsl(180.0, 0.5, 0.5, 255), [64, 191, 191, 255]);
+	assert_eq!(hsl(480.0, 1.0, 0.5, 255), hsl(120.0, 1.0, 0.5, 255));
+	assert_eq!(hsl(0.0, 1.0, 2.0, 255), [255, 255, 255, 255]);
+}
+
+#[test]
+fn map_color_test() {
+	let mut lut = [[0; 256]; 3];
+	lut[0][255] = 10;
+	lut[1][0] = 20;
+	lut[2][128] = 30;
+	assert_eq!(map_color([255, 0, 128, 64], &lut), [10, 20, 30, 64]);
+}
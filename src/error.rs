@@ -7,5 +7,21 @@ pub enum ViewportError {
 	#[error("no adapter was found to manage the rendering")]
 	AdapterNotFound,
     #[error("error ocurred while rendering")]
-    Rendering
-}
\ No newline at end of file
+    Rendering,
+    #[error("invalid color of length {len}, it must have one, three or four channels")]
+    InvalidColor { len: usize },
+    #[error("index {index} is out of bounds for {len} vertices")]
+    IndexOutOfBounds { index: usize, len: usize },
+    #[error("invalid dimensions {width}x{height}x{depth}, all of them must be greater than zero")]
+    InvalidDimensions { width: u32, height: u32, depth: u32 },
+    #[error("invalid render scale {scale}, it must be greater than zero")]
+    InvalidScale { scale: u32 },
+    #[error("snapshot of {width}x{height} doesn't match the viewport of {expected_width}x{expected_height}")]
+    SnapshotMismatch { width: u32, height: u32, expected_width: u32, expected_height: u32 },
+    #[error("size of {width}x{height} doesn't match the expected size of {expected_width}x{expected_height}")]
+    SizeMismatch { width: u32, height: u32, expected_width: u32, expected_height: u32 },
+    #[error("pixel ({x}, {y}) was drawn out of the screen")]
+    OutOfBounds { x: isize, y: isize },
+    #[error("input/output error: {0}")]
+    Io(std::io::Error),
+}
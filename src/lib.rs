@@ -44,7 +44,7 @@
 //!     }
 //!     Event::RedrawRequested(_) => {
 //!       viewport.fill_triangle((0.0, 0.0, -0.1), (0.0 + i, -0.5 * i/2.0, -0.2), (0.0 + i, -0.3 * i/2.0, -0.2), 
-//!         &[255, 0, 0, 255]);
+//!         [255, 0, 0, 255]);
 //!       viewport.render().expect("render failed");
 //!       viewport.reset_buffer();
 //!     }
@@ -55,14 +55,14 @@
 //! ```
 //! 
 //! ## Coloring
-//! All the library drawing operations request a color in the form of a &[u8; 4] (RGBA). This allows the library to be compatible
-//! with the different color crates (in the examples we use the `rgb` crate).
+//! All the library drawing operations request a [`Color`](color::Color), which is any value that can be converted to
+//! a [u8; 4] (RGBA). It's implemented for RGBA arrays and tuples, RGB arrays (fully opaque), single channel arrays
+//! (fully opaque gray) and `u32` packed as `0xRRGGBBAA`. Implementing the trait allows the library to be compatible
+//! with the different color crates.
 //! 
-//! The array dimmension is not enforced at compilation time but a panic will be thrown if the provided value is not of length four.
-//! But this can probably change in the future, allowing to provide just RGB or single channels.
-//! 
-//! Even if we request the alpha, the crate currently **DOES NOT** have transparency as you expect it.
-//! You can use the alpha to play with the colors but they will always be mixed with the black background, not with whatever color could be behind.
+//! By default the alpha **DOES NOT** have transparency as you expect it, the colors will be mixed with the black background,
+//! not with whatever color could be behind. To composite the colors with the ones already drawn, set the
+//! [`BlendMode::AlphaOver`](blend::BlendMode::AlphaOver) mode with [`set_blend`](viewport::Viewport::set_blend).
 //!
 //! [`draw_line`]: viewport::Viewport::draw_line
 //! [`draw_triangle`]: viewport::Viewport::draw_triangle
@@ -78,15 +78,22 @@
 
 extern crate winit;
 
-use num_traits::{NumAssignOps, NumOps, Unsigned, NumCast};
+use num_traits::{Float, NumAssignOps, NumOps, Unsigned, NumCast};
 
+pub mod blend;
+pub mod camera;
+pub mod color;
 pub mod error;
+#[cfg(feature = "gif")]
+pub mod record;
 pub mod render;
+pub mod time;
+pub mod transform;
 pub mod viewport;
-mod pixel;
 mod util;
 
-type Position = (f32, f32, f32);
+/// Position in the normalized space. The drawings accept any [Coord], but they are computed in `f64`
+type Position<C = f64> = (C, C, C);
 type Voxel<T> = (T, T, T);
 
 /// Trait to englobe unsigned integers to use as PixelSize in the Viewport construcion
@@ -96,4 +103,13 @@ pub trait PixelSize: Unsigned + NumAssignOps + NumOps + NumCast + Copy + Ord  {
         NumCast::from(value).unwrap()
     }
 }
-impl<T: Unsigned + NumAssignOps + NumOps + NumCast + Copy + Ord> PixelSize for T {}
\ No newline at end of file
+impl<T: Unsigned + NumAssignOps + NumOps + NumCast + Copy + Ord> PixelSize for T {}
+
+/// Trait to englobe the floating point types accepted as coordinates of the positions, `f32` and `f64`
+pub trait Coord: Float {
+	#[inline]
+	fn as_f64(self) -> f64 {
+		self.to_f64().unwrap()
+	}
+}
+impl<T: Float> Coord for T {}
\ No newline at end of file
@@ -0,0 +1,149 @@
+//! Contains the recorder to export a sequence of frames as an animated GIF. Requires the `gif` feature.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::ViewportError;
+use crate::viewport::Viewport;
+use crate::PixelSize;
+
+/// Speed of the color quantization of the frames, from 1 (best quality) to 30 (fastest)
+const QUANTIZATION_SPEED: i32 = 10;
+
+/// Recorder collecting the content of a [`Viewport`] frame by frame to encode it as an animated GIF.
+/// The frames are quantized to the GIF palette when added, and the file is only written on [`GifRecorder::finish`].
+///
+/// # Example
+/// ```no_run
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+/// use ferrux_viewport::record::GifRecorder;
+///
+/// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(320, 240, 100)?;
+/// let mut recorder = GifRecorder::new(&viewport, Duration::from_millis(40));
+/// for i in 0..25 {
+///     viewport.reset_buffer();
+///     viewport.draw_point((i as f32 / 25.0, 0.0, 0.0), [255, 0, 0, 255]);
+///     recorder.add_frame(&viewport)?;
+/// }
+/// recorder.finish("clip.gif")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GifRecorder {
+	width: u16,
+	height: u16,
+	delay: u16,
+	frames: Vec<gif::Frame<'static>>,
+}
+
+impl GifRecorder {
+	/// Returns a new recorder for frames with the dimensions of the given viewport.
+	///
+	/// # Arguments
+	/// * `viewport`, viewport to take the dimensions from. The GIF format limits them to 65535 pixels.
+	/// * `delay`, time to show each frame. GIF stores it in hundredths of a second, so it's rounded to them.
+	pub fn new<S: PixelSize, R>(viewport: &Viewport<S, R>, delay: Duration) -> Self {
+		Self {
+			width: viewport.width_px().min(u16::MAX as u32) as u16,
+			height: viewport.height_px().min(u16::MAX as u32) as u16,
+			delay: (delay.as_millis() as f32 / 10.0).round().min(u16::MAX as f32) as u16,
+			frames: Vec::new(),
+		}
+	}
+
+	/// Adds the current content of the buffer of the viewport as the next frame
+	///
+	/// # Error
+	/// If the viewport doesn't have the dimensions of the recorder, like when it has been resized.
+	pub fn add_frame<S: PixelSize, R>(&mut self, viewport: &Viewport<S, R>) -> Result<(), ViewportError> {
+		let (width, height) = (viewport.width_px(), viewport.height_px());
+		if (width, height) != (self.width as u32, self.height as u32) {
+			return Err(ViewportError::SizeMismatch {
+				width,
+				height,
+				expected_width: self.width as u32,
+				expected_height: self.height as u32,
+			});
+		}
+		let mut pixels = viewport.buffer_rgba();
+		let mut frame = gif::Frame::from_rgba_speed(self.width, self.height, &mut pixels, QUANTIZATION_SPEED);
+		frame.delay = self.delay;
+		self.frames.push(frame);
+		Ok(())
+	}
+
+	/// Returns the number of frames recorded
+	pub fn len(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// Returns if no frame has been recorded yet
+	pub fn is_empty(&self) -> bool {
+		self.frames.is_empty()
+	}
+
+	/// Encodes the recorded frames as a GIF looping forever and writes it in the given path
+	///
+	/// # Error
+	/// If the file can't be created or the frames can't be encoded.
+	pub fn finish(self, path: impl AsRef<Path>) -> Result<(), ViewportError> {
+		let file = BufWriter::new(File::create(path).map_err(ViewportError::Io)?);
+		let mut encoder = gif::Encoder::new(file, self.width, self.height, &[]).map_err(encoding_error)?;
+		encoder.set_repeat(gif::Repeat::Infinite).map_err(encoding_error)?;
+		for frame in &self.frames {
+			encoder.write_frame(frame).map_err(encoding_error)?;
+		}
+		encoder.into_inner().map_err(ViewportError::Io)?;
+		Ok(())
+	}
+}
+
+/// Maps the errors of the encoder to the ones of the library
+fn encoding_error(error: gif::EncodingError) -> ViewportError {
+	match error {
+		gif::EncodingError::Io(e) => ViewportError::Io(e),
+		e => ViewportError::Io(std::io::Error::other(e)),
+	}
+}
+
+#[test]
+fn record_test() {
+	let mut viewport = crate::viewport::ViewportFactory::test(4, 2, 10);
+	let mut recorder = GifRecorder::new(&viewport, Duration::from_millis(50));
+	viewport.draw_point_px((0, 0, 0), [255, 0, 0, 255]);
+	recorder.add_frame(&viewport).unwrap();
+	viewport.draw_point_px((3, 1, 0), [0, 0, 255, 255]);
+	recorder.add_frame(&viewport).unwrap();
+	assert_eq!(recorder.len(), 2);
+
+	let path = std::env::temp_dir().join("ferrux_viewport_record.gif");
+	recorder.finish(&path).unwrap();
+
+	let mut options = gif::DecodeOptions::new();
+	options.set_color_output(gif::ColorOutput::RGBA);
+	let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+	assert_eq!((decoder.width(), decoder.height()), (4, 2));
+	let mut frames = 0;
+	while let Some(frame) = decoder.read_next_frame().unwrap() {
+		assert_eq!(frame.delay, 5);
+		frames += 1;
+	}
+	std::fs::remove_file(&path).unwrap();
+	assert_eq!(frames, 2);
+}
+
+#[test]
+fn record_mismatch_test() {
+	let viewport = crate::viewport::ViewportFactory::test(4, 2, 10);
+	let mut recorder = GifRecorder::new(&viewport, Duration::from_millis(50));
+	let other = crate::viewport::ViewportFactory::test(2, 2, 10);
+	assert!(matches!(
+		recorder.add_frame(&other),
+		Err(ViewportError::SizeMismatch { width: 2, height: 2, expected_width: 4, expected_height: 2 })
+	));
+	assert!(recorder.is_empty());
+}
@@ -0,0 +1,109 @@
+use log::info;
+
+use crate::{color::to_channels, error::ViewportError};
+
+use super::{copy_frame, copy_region, pack_frame, Render, Resize};
+
+/// Renderer drawing into an in-memory buffer, so it doesn't need any window.
+/// It keeps the last rendered frame to let the user read it.
+///
+/// The frame has `N` channels per pixel, RGBA by default. It can also be RGB with `3` or grayscale with `1`,
+/// packing the colors with [`to_channels`](crate::color::to_channels). Any other count fails to build.
+///
+/// ```compile_fail
+/// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless_channels::<2>(640, 480, 100).unwrap();
+/// viewport.render().unwrap();
+/// ```
+pub struct MemoryRenderer<const N: usize = 4> {
+    frame: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl<const N: usize> MemoryRenderer<N> {
+    pub fn new(width: u32, height: u32) -> Self {
+        info!("[MemoryRenderer] Width: {}. Height: {}. Channels: {}", &width, &height, N);
+        Self {
+            frame: vec![0; (width * height) as usize * N],
+            width,
+            height,
+        }
+    }
+
+    /// Returns the last rendered frame as the bytes of its `N` channels, row by row
+    pub fn frame(&self) -> &[u8] {
+        &self.frame
+    }
+
+    /// Returns the width and height of the frame
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl<const N: usize> Render for MemoryRenderer<N> {
+    fn render(&mut self, buffer: &[[u8; 4]]) -> Result<(), ViewportError> {
+        if N == 4 {
+            copy_frame(&mut self.frame, buffer);
+        } else {
+            pack_frame::<N>(&mut self.frame, buffer);
+        }
+        Ok(())
+    }
+
+    fn render_region(
+        &mut self,
+        buffer: &[[u8; 4]],
+        width: u32,
+        bounds: (u32, u32, u32, u32),
+    ) -> Result<(), ViewportError> {
+        copy_region::<N>(&mut self.frame, self.width, buffer, width, bounds);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), ViewportError> {
+        let color = to_channels::<N>(color);
+        for pixel in self.frame.chunks_exact_mut(N) {
+            pixel.copy_from_slice(&color);
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Resize<u32> for MemoryRenderer<N> {
+    fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.frame = vec![0; (width * height) as usize * N];
+    }
+}
+
+#[test]
+fn render_matches_pixel_copy() {
+    let buffer: Vec<[u8; 4]> = (0..12u8).map(|i| [i, i * 2, i * 3, 255 - i]).collect();
+    let mut renderer = MemoryRenderer::<4>::new(4, 3);
+    renderer.render(&buffer).unwrap();
+
+    let mut expected = vec![0; 4 * 3 * 4];
+    for (pixel, color) in expected.chunks_exact_mut(4).zip(buffer.iter()) {
+        pixel.copy_from_slice(color);
+    }
+    assert_eq!(renderer.frame(), expected.as_slice());
+
+    // a shorter buffer keeps the rest of the frame
+    renderer.render(&[[1, 2, 3, 4]]).unwrap();
+    assert_eq!(renderer.frame()[..8], [1, 2, 3, 4, 1, 2, 3, 254]);
+}
+
+#[test]
+fn render_grayscale_frame() {
+    let mut renderer = MemoryRenderer::<1>::new(2, 2);
+    renderer.clear([50, 50, 50, 255]).unwrap();
+    assert_eq!(renderer.frame(), [50, 50, 50, 50]);
+
+    renderer.render(&[[200, 200, 200, 255], [255, 255, 255, 0]]).unwrap();
+    assert_eq!(renderer.frame(), [200, 0, 50, 50]);
+
+    renderer.render_region(&[[10, 10, 10, 255]; 4], 2, (1, 1, 1, 1)).unwrap();
+    assert_eq!(renderer.frame(), [200, 0, 50, 10]);
+}
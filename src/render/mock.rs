@@ -1,21 +1,32 @@
-use crate::pixel::Pixel;
-
 use super::{Render, Resize};
 
+#[derive(Default)]
 pub struct MockRenderer {
 	pub render_calls: i32,
 	pub clear_calls: i32,
 	pub size: (u32, u32),
+	pub last_frame: Vec<[u8; 4]>,
+	pub last_region: Option<(u32, u32, u32, u32)>,
+	pub clear_color: [u8; 4],
 }
 
 impl Render for MockRenderer {
-    fn render(&mut self, _: &[Pixel]) -> Result<(), crate::error::ViewportError> {
+    fn render(&mut self, buffer: &[[u8; 4]]) -> Result<(), crate::error::ViewportError> {
         self.render_calls += 1;
+		self.last_frame = buffer.to_vec();
 		Ok(())
     }
 
-    fn clear(&mut self) -> Result<(), crate::error::ViewportError> {
+    fn render_region(&mut self, buffer: &[[u8; 4]], _width: u32, bounds: (u32, u32, u32, u32)) -> Result<(), crate::error::ViewportError> {
+        self.render_calls += 1;
+		self.last_frame = buffer.to_vec();
+		self.last_region = Some(bounds);
+		Ok(())
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), crate::error::ViewportError> {
         self.clear_calls += 1;
+		self.clear_color = color;
 		Ok(())
     }
 }
@@ -25,9 +36,3 @@ impl Resize<u32> for MockRenderer {
         self.size = (width, height);
     }
 }
-
-impl Default for MockRenderer {
-    fn default() -> Self {
-        Self { render_calls: Default::default(), clear_calls: Default::default(), size: Default::default() }
-    }
-}
\ No newline at end of file
@@ -1,19 +1,100 @@
 //! Contains the logic the operate with the different library options
 
-use crate::{error::ViewportError, PixelSize, pixel::Pixel};
+use crate::{color::to_channels, error::ViewportError, PixelSize};
 
+mod memory;
+#[cfg(feature = "web")]
+mod web;
 mod winit;
 
 #[cfg(test)]
 pub(crate) mod mock;
 
-pub(crate) use self::winit::WinitRenderer;
+pub use self::memory::MemoryRenderer;
+#[cfg(feature = "web")]
+pub use self::web::WebRenderer;
+pub use self::winit::WinitRenderer;
 
 pub trait Render {
-	fn render(&mut self, buffer: &[Pixel]) -> Result<(), ViewportError>;
-	fn clear(&mut self) -> Result<(), ViewportError>;
+	fn render(&mut self, buffer: &[[u8; 4]]) -> Result<(), ViewportError>;
+	/// Renders only the rectangle of the buffer between the `(min_x, min_y, max_x, max_y)` corners, both included.
+	/// The buffer is row by row with rows of the given width.
+	fn render_region(&mut self, buffer: &[[u8; 4]], width: u32, bounds: (u32, u32, u32, u32)) -> Result<(), ViewportError>;
+	fn clear(&mut self, color: [u8; 4]) -> Result<(), ViewportError>;
+}
+
+/// Copies the colors of the buffer into the RGBA bytes of the frame with a single copy of the whole slice.
+/// If their sizes don't match only the common part is copied.
+pub(crate) fn copy_frame(frame: &mut [u8], buffer: &[[u8; 4]]) {
+	let colors = buffer.as_flattened();
+	let len = frame.len().min(colors.len());
+	frame[..len].copy_from_slice(&colors[..len]);
+}
+
+/// Packs the colors of the buffer into the bytes of a frame of `N` channels, see [`to_channels`].
+/// If their sizes don't match only the common part is packed.
+pub(crate) fn pack_frame<const N: usize>(frame: &mut [u8], buffer: &[[u8; 4]]) {
+	for (pixel, &color) in frame.chunks_exact_mut(N).zip(buffer) {
+		pixel.copy_from_slice(&to_channels::<N>(color));
+	}
+}
+
+/// Copies the rectangle between the `(min_x, min_y, max_x, max_y)` corners, both included, from the buffer
+/// with rows of `width` pixels into the bytes of the frame of `N` channels with rows of `frame_width` pixels.
+/// The pixels out of any of both are skipped.
+pub(crate) fn copy_region<const N: usize>(
+	frame: &mut [u8],
+	frame_width: u32,
+	buffer: &[[u8; 4]],
+	width: u32,
+	(x0, y0, x1, y1): (u32, u32, u32, u32),
+) {
+	for y in y0..=y1 {
+		for x in x0..=x1 {
+			let frame_index = (y * frame_width + x) as usize * N;
+			if let (Some(pixel), Some(&color)) = (
+				frame.get_mut(frame_index..frame_index + N),
+				buffer.get((y * width + x) as usize),
+			) {
+				pixel.copy_from_slice(&to_channels::<N>(color));
+			}
+		}
+	}
 }
 
 pub trait Resize<S: PixelSize> {
 	fn resize(&mut self, width: S, height: S);
-}
\ No newline at end of file
+}
+#[test]
+fn copy_frame_test() {
+	let mut frame = vec![0; 12];
+	copy_frame(&mut frame, &[[1, 2, 3, 4], [5, 6, 7, 8]]);
+	assert_eq!(frame, [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0]);
+	copy_frame(&mut frame[..4], &[[9, 9, 9, 9], [9, 9, 9, 9]]);
+	assert_eq!(frame, [9, 9, 9, 9, 5, 6, 7, 8, 0, 0, 0, 0]);
+}
+
+#[test]
+fn copy_region_test() {
+	let buffer: Vec<[u8; 4]> = (0..9).map(|i| [i; 4]).collect();
+	let mut frame = vec![0; 9 * 4];
+	copy_region::<4>(&mut frame, 3, &buffer, 3, (1, 1, 2, 2));
+	let expected: Vec<u8> = [0, 0, 0, 0, 4, 5, 0, 7, 8].iter().flat_map(|&i| [i; 4]).collect();
+	assert_eq!(frame, expected);
+
+	let mut frame = vec![0; 2 * 4];
+	copy_region::<4>(&mut frame, 1, &buffer, 3, (0, 0, 2, 1));
+	assert_eq!(frame, [0, 0, 0, 0, 3, 3, 3, 3]);
+
+	let buffer: Vec<[u8; 4]> = (0..9).map(|i| [i, i, i, 255]).collect();
+	let mut frame = vec![0; 3];
+	copy_region::<1>(&mut frame, 3, &buffer, 3, (1, 0, 2, 0));
+	assert_eq!(frame, [0, 1, 2]);
+}
+
+#[test]
+fn pack_frame_test() {
+	let mut frame = vec![0; 9];
+	pack_frame::<3>(&mut frame, &[[1, 2, 3, 255], [4, 5, 6, 255]]);
+	assert_eq!(frame, [1, 2, 3, 4, 5, 6, 0, 0, 0]);
+}
@@ -0,0 +1,103 @@
+use log::{error, info};
+use wasm_bindgen::{Clamped, JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
+
+use crate::error::ViewportError;
+
+use super::{copy_frame, copy_region, Render, Resize};
+
+/// Renderer drawing into the 2D context of an [HtmlCanvasElement], to run in the browser.
+/// The frame is kept with the RGBA layout of [ImageData], so the buffer is copied into it as it is.
+pub struct WebRenderer {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    frame: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl WebRenderer {
+    pub fn new(canvas: HtmlCanvasElement) -> Result<Self, ViewportError> {
+        info!("[WebRenderer] Initializing.");
+
+        let width = canvas.width();
+        let height = canvas.height();
+        info!("[WebRenderer] Width: {}. Height: {}", &width, &height);
+
+        info!("[WebRenderer] Getting the 2D context.");
+        let context = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+            .ok_or(ViewportError::AdapterNotFound)?;
+
+        Ok(Self {
+            canvas,
+            context,
+            frame: vec![0; (width * height * 4) as usize],
+            width,
+            height,
+        })
+    }
+
+    /// Uploads the rectangle of the frame between the `(min_x, min_y, max_x, max_y)` corners to the canvas
+    fn put_frame(&self, (x0, y0, x1, y1): (u32, u32, u32, u32)) -> Result<(), ViewportError> {
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&self.frame), self.width, self.height)
+            .and_then(|image| {
+                self.context
+                    .put_image_data_with_dirty_x_and_dirty_y_and_dirty_width_and_dirty_height(
+                        &image,
+                        0.0,
+                        0.0,
+                        x0 as f64,
+                        y0 as f64,
+                        (x1 - x0 + 1) as f64,
+                        (y1 - y0 + 1) as f64,
+                    )
+            })
+            .map_err(|e: JsValue| {
+                error!("putImageData() failed: {:?}", e);
+                ViewportError::Rendering
+            })
+    }
+
+    /// Returns the bounds of the whole frame
+    fn full_bounds(&self) -> (u32, u32, u32, u32) {
+        (0, 0, self.width.saturating_sub(1), self.height.saturating_sub(1))
+    }
+}
+
+impl Render for WebRenderer {
+    fn render(&mut self, buffer: &[[u8; 4]]) -> Result<(), ViewportError> {
+        copy_frame(&mut self.frame, buffer);
+        self.put_frame(self.full_bounds())
+    }
+
+    fn render_region(
+        &mut self,
+        buffer: &[[u8; 4]],
+        width: u32,
+        bounds: (u32, u32, u32, u32),
+    ) -> Result<(), ViewportError> {
+        copy_region::<4>(&mut self.frame, self.width, buffer, width, bounds);
+        self.put_frame(bounds)
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), ViewportError> {
+        for pixel in self.frame.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+        self.put_frame(self.full_bounds())
+    }
+}
+
+impl Resize<u32> for WebRenderer {
+    fn resize(&mut self, width: u32, height: u32) {
+        self.canvas.set_width(width);
+        self.canvas.set_height(height);
+        self.width = width;
+        self.height = height;
+        self.frame = vec![0; (width * height * 4) as usize];
+    }
+}
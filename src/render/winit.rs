@@ -2,9 +2,9 @@ use log::{error, info};
 use pixels::{Pixels, SurfaceTexture};
 use winit::window::Window;
 
-use crate::{error::ViewportError, pixel::Pixel};
+use crate::error::ViewportError;
 
-use super::{Render, Resize};
+use super::{copy_frame, copy_region, Render, Resize};
 
 /// Renderer able to work with Winit's [Window]
 pub struct WinitRenderer {
@@ -33,15 +33,8 @@ impl WinitRenderer {
 }
 
 impl Render for WinitRenderer {
-    fn render(&mut self, buffer: &[Pixel]) -> Result<(), ViewportError> {
-        for (pixel, color) in self
-            .pixels
-            .get_frame()
-            .chunks_exact_mut(4)
-            .zip(buffer.iter())
-        {
-            pixel.copy_from_slice(&color.color);
-        }
+    fn render(&mut self, buffer: &[[u8; 4]]) -> Result<(), ViewportError> {
+        copy_frame(self.pixels.get_frame(), buffer);
 
         self.pixels.render().map_err(|e| {
             error!("pixels.render() failed: {:?}", e);
@@ -49,9 +42,23 @@ impl Render for WinitRenderer {
         })
     }
 
-    fn clear(&mut self) -> Result<(), crate::error::ViewportError> {
+    fn render_region(
+        &mut self,
+        buffer: &[[u8; 4]],
+        width: u32,
+        bounds: (u32, u32, u32, u32),
+    ) -> Result<(), ViewportError> {
+        copy_region::<4>(self.pixels.get_frame(), width, buffer, width, bounds);
+
+        self.pixels.render().map_err(|e| {
+            error!("pixels.render() failed: {:?}", e);
+            ViewportError::Rendering
+        })
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), crate::error::ViewportError> {
         for pixel in self.pixels.get_frame().chunks_exact_mut(4) {
-			pixel.copy_from_slice(&[0, 0, 0, 0]);
+			pixel.copy_from_slice(&color);
 		}
 
 		self.pixels.render().map_err(|e| {
@@ -0,0 +1,83 @@
+//! Contains the helpers to keep track of the time between frames
+
+use std::time::{Duration, Instant};
+
+/// Clock measuring the time between consecutive frames, to drive the animations.
+/// It can be updated by the [`Viewport`](crate::viewport::Viewport) on each render, see
+/// [`Viewport::set_frame_clock`](crate::viewport::Viewport::set_frame_clock), or manually with [`FrameClock::tick`].
+///
+/// # Example
+/// ```
+/// use ferrux_viewport::time::FrameClock;
+///
+/// let mut clock = FrameClock::new();
+/// clock.tick();
+/// clock.tick();
+/// assert!(clock.delta_seconds() >= 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameClock {
+	last: Option<Instant>,
+	delta: Duration,
+}
+
+impl FrameClock {
+	/// Returns a new clock without any frame
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Marks the current instant as the start of a new frame
+	pub fn tick(&mut self) {
+		self.tick_at(Instant::now());
+	}
+
+	/// Marks the given instant as the start of a new frame. The first frame has no delta.
+	pub fn tick_at(&mut self, now: Instant) {
+		if let Some(last) = self.last {
+			self.delta = now.saturating_duration_since(last);
+		}
+		self.last = Some(now);
+	}
+
+	/// Returns the time between the last two frames
+	pub fn delta(&self) -> Duration {
+		self.delta
+	}
+
+	/// Returns the time between the last two frames in seconds
+	pub fn delta_seconds(&self) -> f32 {
+		self.delta.as_secs_f32()
+	}
+
+	/// Returns the frames per second corresponding to the last delta, or zero if there's no delta yet
+	pub fn fps(&self) -> f32 {
+		if self.delta.is_zero() {
+			return 0.0;
+		}
+		1.0 / self.delta_seconds()
+	}
+}
+
+#[test]
+fn first_tick_test() {
+	let mut clock = FrameClock::new();
+	clock.tick_at(Instant::now());
+	assert_eq!(clock.delta_seconds(), 0.0);
+	assert_eq!(clock.fps(), 0.0);
+}
+
+#[test]
+fn delta_test() {
+	let start = Instant::now();
+	let mut clock = FrameClock::new();
+	clock.tick_at(start);
+	clock.tick_at(start + Duration::from_millis(20));
+	assert_eq!(clock.delta(), Duration::from_millis(20));
+	assert_eq!(clock.delta_seconds(), 0.02);
+	assert_eq!(clock.fps(), 50.0);
+
+	clock.tick_at(start + Duration::from_millis(45));
+	assert_eq!(clock.delta(), Duration::from_millis(25));
+	assert_eq!(clock.fps(), 40.0);
+}
@@ -0,0 +1,112 @@
+//! Contains the affine transforms to place the shapes drawn in the viewport
+
+use crate::{Coord, Position};
+
+/// Affine transform of the `x` and `y` axes in the normalized space, the `z` is kept untouched.
+/// The transforms can be combined with [`Transform2D::then`] to build complex ones. They are computed in `f64`,
+/// so the positions of any [Coord] keep their precision.
+///
+/// # Example
+/// ```
+/// use ferrux_viewport::transform::Transform2D;
+///
+/// let transform = Transform2D::scale(0.5, 0.5).then(Transform2D::translate(0.25, 0.0));
+/// assert_eq!(transform.apply((1.0, 1.0, 0.3)), (0.75, 0.5, 0.3));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2D {
+	matrix: [[f64; 3]; 2],
+}
+
+impl Transform2D {
+	/// Returns the transform leaving the positions as they are
+	pub fn identity() -> Self {
+		Self { matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] }
+	}
+
+	/// Returns the transform moving the positions by the given offset
+	pub fn translate(x: f32, y: f32) -> Self {
+		Self { matrix: [[1.0, 0.0, x as f64], [0.0, 1.0, y as f64]] }
+	}
+
+	/// Returns the transform scaling the positions by the given factor of each axis, relative to the origin
+	pub fn scale(x: f32, y: f32) -> Self {
+		Self { matrix: [[x as f64, 0.0, 0.0], [0.0, y as f64, 0.0]] }
+	}
+
+	/// Returns the transform rotating the positions around the origin by the given angle, in radians.
+	/// As the `y` axis goes from north to south, positive angles rotate clockwise on screen.
+	pub fn rotate(angle: f32) -> Self {
+		let (sin, cos) = (angle as f64).sin_cos();
+		Self { matrix: [[cos, -sin, 0.0], [sin, cos, 0.0]] }
+	}
+
+	/// Returns the transform applying this one first and then the given one
+	pub fn then(&self, next: Transform2D) -> Self {
+		let [[a, b, c], [d, e, f]] = next.matrix;
+		let [[g, h, i], [j, k, l]] = self.matrix;
+		Self {
+			matrix: [
+				[a * g + b * j, a * h + b * k, a * i + b * l + c],
+				[d * g + e * j, d * h + e * k, d * i + e * l + f],
+			],
+		}
+	}
+
+	/// Returns the position resulting of applying the transform to the given one
+	pub fn apply<C: Coord>(&self, (x, y, z): Position<C>) -> Position<C> {
+		let [[a, b, c], [d, e, f]] = self.matrix;
+		let (x, y) = (x.as_f64(), y.as_f64());
+		let coord = |value: f64| C::from(value).unwrap();
+		(coord(a * x + b * y + c), coord(d * x + e * y + f), z)
+	}
+}
+
+impl Default for Transform2D {
+	fn default() -> Self {
+		Self::identity()
+	}
+}
+
+#[cfg(test)]
+fn assert_close((ax, ay, az): Position, (bx, by, bz): Position) {
+	assert!((ax - bx).abs() < 1e-6 && (ay - by).abs() < 1e-6 && (az - bz).abs() < 1e-6, "{:?} != {:?}", (ax, ay, az), (bx, by, bz));
+}
+
+#[test]
+fn identity_test() {
+	assert_eq!(Transform2D::identity().apply((0.5, -0.25, 0.1)), (0.5, -0.25, 0.1));
+	assert_eq!(Transform2D::default(), Transform2D::identity());
+}
+
+#[test]
+fn translate_test() {
+	assert_eq!(Transform2D::translate(0.5, -0.25).apply((0.25, 0.25, 0.1)), (0.75, 0.0, 0.1));
+}
+
+#[test]
+fn scale_test() {
+	assert_eq!(Transform2D::scale(2.0, 0.5).apply((0.25, 0.5, 0.1)), (0.5, 0.25, 0.1));
+}
+
+#[test]
+fn rotate_test() {
+	assert_close(Transform2D::rotate(std::f32::consts::FRAC_PI_2).apply((0.5, 0.0, 0.1)), (0.0, 0.5, 0.1));
+	assert_close(Transform2D::rotate(std::f32::consts::PI).apply((0.5, 0.25, 0.1)), (-0.5, -0.25, 0.1));
+}
+
+#[test]
+fn apply_f64_test() {
+	let (x, y, z) = Transform2D::translate(0.25, 0.0).apply((-0.125f64 - 1e-10, 0.5, 0.1));
+	assert!(x < 0.125 && (x - 0.125).abs() < 1e-9, "{} is not right before 0.125", x);
+	assert_eq!((y, z), (0.5, 0.1));
+	assert_eq!(Transform2D::scale(2.0, 0.5).apply((0.25f32, 0.5f32, 0.1f32)), (0.5, 0.25, 0.1));
+}
+
+#[test]
+fn then_test() {
+	let transform = Transform2D::translate(0.5, 0.0).then(Transform2D::scale(0.5, 0.5));
+	assert_eq!(transform.apply((0.5, 0.5, 0.0)), (0.5, 0.25, 0.0));
+	let transform = Transform2D::scale(0.5, 0.5).then(Transform2D::translate(0.5, 0.0));
+	assert_eq!(transform.apply((0.5, 0.5, 0.0)), (0.75, 0.25, 0.0));
+}
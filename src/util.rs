@@ -1,12 +1,124 @@
-use crate::{Position, Voxel};
+use crate::error::ViewportError;
+use bresenham_zip::build_zip;
+use line_drawing::Bresenham;
+use crate::{Coord, Position, Voxel};
+use crate::viewport::CoordSpace;
 
-/// Converts the normalized position into the pixel equivalent in the given screen
+/// Options of the conversion of the normalized positions into pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PixelMapping {
+	/// The `y` axis goes from south to north, so the rows are inverted
+	pub y_up: bool,
+	/// The positions out of the screen are moved to its closest edge
+	pub clamp: bool,
+	/// The radii of the circles and ellipses use the same scale in both axes
+	pub preserve_aspect: bool,
+	/// The range of the `x` and `y` coordinates covering the screen
+	pub coord_space: CoordSpace,
+}
+
+/// Converts the position of any [Coord] into `f64`, the precision used to compute the drawings
+#[inline]
+pub fn to_f64<C: Coord>((x, y, z): Position<C>) -> Position {
+	(x.as_f64(), y.as_f64(), z.as_f64())
+}
+
+/// Converts the normalized position into the pixel equivalent in the given screen.
+/// The conversion is done in `f64` to keep the precision of any [Coord]. The pixels are signed, so the positions
+/// out of the screen stay out of it on every side unless they are clamped.
+#[inline]
+pub fn to_pixel<C: Coord>(
+	(x, y, z): (C, C, C),
+	(width, height, depth): (usize, usize, usize),
+	mapping: PixelMapping,
+) -> Voxel<isize> {
+	let (x, y, z) = (x.as_f64(), y.as_f64(), z.as_f64());
+	let (x, y) = match mapping.coord_space {
+		CoordSpace::CenteredNormalized => ((x + 1.0) * 0.5, (y + 1.0) * 0.5),
+		CoordSpace::UnitTopLeft => (x, y),
+	};
+	let w = x * (width as f64);
+	let h = y * (height as f64);
+	// the far edge of the screen belongs to the last pixel and not to the one right after it
+	let edge = |value: f64, size: usize| {
+		if value == size as f64 { size as isize - 1 } else { value.floor() as isize }
+	};
+	let (w, h) = (edge(w, width), edge(h, height));
+	let (last_w, last_h) = (width as isize - 1, height as isize - 1);
+	let (w, h) = if mapping.clamp {
+		(w.clamp(0, last_w.max(0)), h.clamp(0, last_h.max(0)))
+	} else {
+		(w, h)
+	};
+	let h = if mapping.y_up { last_h - h } else { h };
+	(w, h, to_depth(z, depth) as isize)
+}
+
+/// Converts the normalized `z` coordinate into the depth stored in a buffer of the given depth range
 #[inline]
-pub fn to_pixel((x, y, z): Position, (width, height, depth): (usize, usize, usize)) -> Voxel<usize> {
-	let w = (x + 1.0) * 0.5 * (width as f32);
-	let h = (y + 1.0) * 0.5 * (height as f32);
-	let d = (z + 1.0) * 0.5 * (depth as f32);
-	(w as usize, h as usize, d as usize)
+pub fn to_depth(z: f64, depth: usize) -> usize {
+	((z + 1.0) * 0.5 * (depth as f64)) as usize
+}
+
+/// Converts the pixel of the given screen into its normalized position, the inverse of [`to_pixel`].
+/// The pixels are taken by their center, so the fractional part of the coordinates moves within the pixel.
+#[inline]
+pub fn to_normalized((x, y): (f64, f64), (width, height): (usize, usize), mapping: PixelMapping) -> (f64, f64) {
+	let y = if mapping.y_up { height as f64 - 1.0 - y } else { y };
+	let (x, y) = ((x + 0.5) / width as f64, (y + 0.5) / height as f64);
+	match mapping.coord_space {
+		CoordSpace::CenteredNormalized => (x * 2.0 - 1.0, y * 2.0 - 1.0),
+		CoordSpace::UnitTopLeft => (x, y),
+	}
+}
+
+/// Clips the segment to the `[-1.0, 1.0]` depth range, interpolating the `x` and `y` of the endpoints
+/// at the clipping planes. Returns [None] if the whole segment is out of the range.
+pub fn clip_z(start: Position, end: Position) -> Option<(Position, Position)> {
+	let at = |t: f64| (start.0 + t * (end.0 - start.0), start.1 + t * (end.1 - start.1), start.2 + t * (end.2 - start.2));
+	let (mut t_start, mut t_end) = (0.0_f64, 1.0_f64);
+	let dz = end.2 - start.2;
+	for (distance, direction) in [(start.2 + 1.0, dz), (1.0 - start.2, -dz)] {
+		if direction == 0.0 {
+			if distance < 0.0 {
+				return None;
+			}
+		} else if direction > 0.0 {
+			t_start = t_start.max(-distance / direction);
+		} else {
+			t_end = t_end.min(-distance / direction);
+		}
+	}
+	if t_start > t_end {
+		return None;
+	}
+	let clipped_start = if t_start > 0.0 { at(t_start) } else { start };
+	let clipped_end = if t_end < 1.0 { at(t_end) } else { end };
+	Some((clipped_start, clipped_end))
+}
+
+/// Calculates the pixels of the line between both points. The depth of each pixel is interpolated along the line
+/// instead of stepped, so short lines with depth changes keep it in every pixel. A single pixel line keeps the highest depth.
+/// The line is always walked from its leftmost (or topmost) end, so the pixels and their depths are the same no matter
+/// the order of the points.
+pub fn line_voxels(start: Voxel<isize>, end: Voxel<isize>) -> impl Iterator<Item = Voxel<isize>> {
+	let (start, end) = if (end.0, end.1) < (start.0, start.1) { (end, start) } else { (start, end) };
+	let steps = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+	let dz = (end.2 - start.2) as f32;
+	Bresenham::new((start.0, start.1), (end.0, end.1)).enumerate().map(move |(i, (x, y))| {
+		let z = if steps == 0 {
+			start.2.max(end.2)
+		} else {
+			(start.2 as f32 + dz * i as f32 / steps as f32).round() as isize
+		};
+		(x, y, z)
+	})
+}
+
+/// Converts pixel coordinates given by the user into the type returned by [to_pixel]
+#[inline]
+pub fn px_to_voxel((x, y, z): Voxel<u32>) -> Voxel<usize> {
+	(x as usize, y as usize, z as usize)
 }
 
 /// Converts the return type of [to_pixel] to allow it to work with the Bresenham crate
@@ -31,7 +143,23 @@ pub fn calculate_intersection(top: Voxel<isize>, mid: Voxel<isize>, bot: Voxel<i
 	let diff_z = bot.2 as f32 - top.2 as f32;
 	let x = top.0 as f32 + (diff_y_mid / diff_y_bot) * diff_x;
 	let z = top.2 as f32 + (diff_y_mid / diff_y_bot) * diff_z;
-	(x as isize, mid.1 as isize, z as isize)
+	(x as isize, mid.1, z as isize)
+}
+
+/// Checks if the triangle formed by the points has no area in the XY plane, being all of them collinear
+#[inline]
+pub fn is_degenerate(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>) -> bool {
+	(b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1) == 0
+}
+
+/// Checks if the quad formed by the points in order crosses itself in the XY plane, having any pair
+/// of opposite sides intersecting
+pub fn is_self_intersecting(a: Position, b: Position, c: Position, d: Position) -> bool {
+	let orientation = |p: Position, q: Position, r: Position| ((q.0 - p.0) * (r.1 - p.1) - (r.0 - p.0) * (q.1 - p.1)).signum();
+	let cross = |p1: Position, p2: Position, q1: Position, q2: Position| {
+		orientation(p1, p2, q1) * orientation(p1, p2, q2) < 0.0 && orientation(q1, q2, p1) * orientation(q1, q2, p2) < 0.0
+	};
+	cross(a, b, c, d) || cross(b, c, d, a)
 }
 
 /// Receives three points and returns them sorted by Y value.
@@ -39,14 +167,285 @@ pub fn calculate_intersection(top: Voxel<isize>, mid: Voxel<isize>, bot: Voxel<i
 #[inline]
 pub fn sort_vectors(p1: Voxel<isize>, p2: Voxel<isize>, p3: Voxel<isize>) -> (Voxel<isize>, Voxel<isize>, Voxel<isize>) {
 	let mut points = [p1, p2, p3];
-	points.sort_by(|&a, &b| a.1.cmp(&b.1));
+	points.sort_by_key(|point| point.1);
 	(points[2], points[1], points[0])
 }
 
+/// Returns the horizontal spans covering the triangle, the same ones used to fill it with flat Y triangles.
+/// A triangle without area is returned as its three edges.
+pub fn triangle_spans(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>) -> Vec<(Voxel<isize>, Voxel<isize>)> {
+	if is_degenerate(a, b, c) {
+		return vec![(a, b), (b, c), (c, a)];
+	}
+	let flat_spans = |peak: Voxel<isize>, side_a: Voxel<isize>, side_b: Voxel<isize>| {
+		build_zip!(3D:Y - peak -> side_a, side_b)
+			.expect("Side points of a flat triangle should share the same Y value")
+			.collect::<Vec<_>>()
+	};
+	let (a, b, c) = sort_vectors(a, b, c);
+	match b {
+		(_, y, _) if y == c.1 => flat_spans(a, b, c),
+		(_, y, _) if y == a.1 => flat_spans(c, a, b),
+		_ => {
+			let intersection = calculate_intersection(c, b, a);
+			let mut spans = flat_spans(a, b, intersection);
+			spans.extend(flat_spans(c, b, intersection));
+			spans
+		}
+	}
+}
+
+/// Returns the horizontal spans covering the triangle, one per row from the top vertex to the bottom one.
+/// The ends of each span are the crossings of the row with the edges, interpolating their `x` and `z`, so all the
+/// triangles are handled the same way no matter their orientation, even the ones without area.
+pub fn scanline_spans(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>) -> Vec<(Voxel<isize>, Voxel<isize>)> {
+	let top = a.1.min(b.1).min(c.1);
+	let bottom = a.1.max(b.1).max(c.1);
+	(top..=bottom)
+		.filter_map(|y| {
+			let crossings = [(a, b), (b, c), (c, a)].into_iter().flat_map(|(from, to)| {
+				let (upper, lower) = if from.1 <= to.1 { (from, to) } else { (to, from) };
+				match (upper.1, lower.1) {
+					(y0, y1) if y < y0 || y > y1 => vec![],
+					(y0, y1) if y0 == y1 => vec![upper, lower],
+					_ => {
+						let (x, z) = edge_crossing(upper, lower, y);
+						vec![(x, y, z)]
+					}
+				}
+			});
+			crossings.fold(None, |span: Option<(Voxel<isize>, Voxel<isize>)>, crossing| {
+				Some(match span {
+					None => (crossing, crossing),
+					Some((left, right)) => (
+						if crossing.0 < left.0 { crossing } else { left },
+						if crossing.0 > right.0 { crossing } else { right },
+					),
+				})
+			})
+		})
+		.collect()
+}
+
+/// Calculates the points of the first octant of a circle of the given radius centered in the origin
+/// using the midpoint circle algorithm. The rest of the circle can be obtained by symmetry.
+pub fn circle_octant(radius: isize) -> Vec<(isize, isize)> {
+	let mut points = Vec::new();
+	let (mut x, mut y) = (radius, 0);
+	let mut error = 1 - radius;
+	while x >= y {
+		points.push((x, y));
+		y += 1;
+		if error < 0 {
+			error += 2 * y + 1;
+		} else {
+			x -= 1;
+			error += 2 * (y - x) + 1;
+		}
+	}
+	points
+}
+
+/// Calculates the points of the first quadrant of an ellipse of the given radii centered in the origin
+/// using the midpoint ellipse algorithm. The rest of the ellipse can be obtained by symmetry.
+/// A zero radius degrades the ellipse into a line over the other axis.
+pub fn ellipse_quadrant(rx: isize, ry: isize) -> Vec<(isize, isize)> {
+	if rx == 0 || ry == 0 {
+		return (0..=rx)
+			.flat_map(|x| (0..=ry).map(move |y| (x, y)))
+			.collect();
+	}
+	let (rx2, ry2) = ((rx * rx) as f64, (ry * ry) as f64);
+	let mut points = Vec::new();
+	let (mut x, mut y) = (0, ry);
+	let (mut dx, mut dy) = (0.0, 2.0 * rx2 * y as f64);
+
+	// Region 1, the slope is less than one
+	let mut p = ry2 - rx2 * ry as f64 + 0.25 * rx2;
+	while dx < dy {
+		points.push((x, y));
+		x += 1;
+		dx += 2.0 * ry2;
+		if p < 0.0 {
+			p += dx + ry2;
+		} else {
+			y -= 1;
+			dy -= 2.0 * rx2;
+			p += dx - dy + ry2;
+		}
+	}
+
+	// Region 2, the slope is greater than one
+	p = ry2 * (x as f64 + 0.5).powi(2) + rx2 * ((y - 1) as f64).powi(2) - rx2 * ry2;
+	while y >= 0 {
+		points.push((x, y));
+		y -= 1;
+		dy -= 2.0 * rx2;
+		if p > 0.0 {
+			p += rx2 - dy;
+		} else {
+			x += 1;
+			dx += 2.0 * ry2;
+			p += dx - dy + rx2;
+		}
+	}
+	points
+}
+
+/// Calculates the point of the Bézier curve defined by the given control points at the parameter `t`
+/// using the De Casteljau algorithm
+pub fn bezier_point(controls: &[Position], t: f64) -> Position {
+	let mut points = controls.to_vec();
+	while points.len() > 1 {
+		points = points
+			.windows(2)
+			.map(|w| {
+				let ((x0, y0, z0), (x1, y1, z1)) = (w[0], w[1]);
+				(x0 + (x1 - x0) * t, y0 + (y1 - y0) * t, z0 + (z1 - z0) * t)
+			})
+			.collect();
+	}
+	points[0]
+}
+
+/// Calculates the point of the uniform Catmull-Rom spline segment going from `p1` to `p2` at the parameter `t`,
+/// using `p0` and `p3` as the neighbouring points shaping the tangents
+pub fn catmull_rom_point(p0: Position, p1: Position, p2: Position, p3: Position, t: f64) -> Position {
+	let (t2, t3) = (t * t, t * t * t);
+	let axis = |a: f64, b: f64, c: f64, d: f64| {
+		0.5 * (2.0 * b + (c - a) * t + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2 + (3.0 * b - a - 3.0 * c + d) * t3)
+	};
+	(
+		axis(p0.0, p1.0, p2.0, p3.0),
+		axis(p0.1, p1.1, p2.1, p3.1),
+		axis(p0.2, p1.2, p2.2, p3.2),
+	)
+}
+
+/// Builds the edge table of the polygon formed by the given vertices. Each edge is returned as its top and bottom
+/// points, sorted by the top Y value. Horizontal edges are discarded as they never cross a scanline.
+pub fn edge_table(vertices: &[Voxel<isize>]) -> Vec<(Voxel<isize>, Voxel<isize>)> {
+	let mut edges: Vec<(Voxel<isize>, Voxel<isize>)> = vertices
+		.iter()
+		.zip(vertices.iter().cycle().skip(1))
+		.filter(|(a, b)| a.1 != b.1)
+		.map(|(&a, &b)| if a.1 < b.1 { (a, b) } else { (b, a) })
+		.collect();
+	edges.sort_by_key(|(top, _)| top.1);
+	edges
+}
+
+/// Calculates the point with the given Y value over the edge connecting top and bot
+#[inline]
+pub fn edge_crossing(top: Voxel<isize>, bot: Voxel<isize>, y: isize) -> (isize, isize) {
+	let t = (y - top.1) as f32 / (bot.1 - top.1) as f32;
+	let x = top.0 as f32 + t * (bot.0 - top.0) as f32;
+	let z = top.2 as f32 + t * (bot.2 - top.2) as f32;
+	(x.round() as isize, z.round() as isize)
+}
+
+/// Calculates the depth of the given row linearly interpolating the depths of the top and bottom rows
+#[inline]
+pub fn row_depth(y: isize, (top, top_z): (isize, isize), (bottom, bottom_z): (isize, isize)) -> isize {
+	if bottom == top {
+		top_z
+	} else {
+		top_z + (bottom_z - top_z) * (y - top) / (bottom - top)
+	}
+}
+
+/// Rectangle defined by the `(min_x, min_y, max_x, max_y)` corners
+pub type Bounds = (usize, usize, usize, usize);
+
+/// Calculates the barycentric weights of the point in the triangle. Returns [None] if the point is outside
+/// or the triangle has no area.
+pub fn barycentric(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>, point: (isize, isize)) -> Option<(f32, f32, f32)> {
+	barycentric_weights(a, b, c, point).filter(|&(wa, wb, wc)| wa >= 0.0 && wb >= 0.0 && wc >= 0.0)
+}
+
+/// Calculates the barycentric weights of the point relative to the triangle, negative for the vertices whose
+/// opposite edge leaves the point outside. Returns [None] if the triangle has no area.
+pub fn barycentric_weights(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>, (x, y): (isize, isize)) -> Option<(f32, f32, f32)> {
+	let edge = |from: Voxel<isize>, to: Voxel<isize>| (from.0 - x) * (to.1 - y) - (to.0 - x) * (from.1 - y);
+	let (wa, wb, wc) = (edge(b, c), edge(c, a), edge(a, b));
+	let area = wa + wb + wc;
+	if area == 0 {
+		return None;
+	}
+	let area = area as f32;
+	Some((wa as f32 / area, wb as f32 / area, wc as f32 / area))
+}
+
+/// Calculates the distance in pixels from the point to the closest edge of the triangle, positive inside
+/// and negative outside. The triangle must have area.
+pub fn edge_distance(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>, (x, y): (isize, isize)) -> f32 {
+	let area = (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1);
+	let sign = area.signum() as f32;
+	let distance = |from: Voxel<isize>, to: Voxel<isize>| {
+		let (dx, dy) = ((to.0 - from.0) as f32, (to.1 - from.1) as f32);
+		sign * (dx * (y - from.1) as f32 - dy * (x - from.0) as f32) / dx.hypot(dy)
+	};
+	distance(a, b).min(distance(b, c)).min(distance(c, a))
+}
+
+/// Interpolates the depth of the point in the plane of the triangle, clamped to the depths of its vertices
+/// so the points outside don't go beyond them. The triangle must have area.
+pub fn plane_depth(a: Voxel<isize>, b: Voxel<isize>, c: Voxel<isize>, (x, y): (isize, isize)) -> isize {
+	let edge = |from: Voxel<isize>, to: Voxel<isize>| (from.0 - x) * (to.1 - y) - (to.0 - x) * (from.1 - y);
+	let (wa, wb, wc) = (edge(b, c), edge(c, a), edge(a, b));
+	let z = (wa * a.2 + wb * b.2 + wc * c.2) as f32 / (wa + wb + wc) as f32;
+	(z.round() as isize).clamp(a.2.min(b.2).min(c.2), a.2.max(b.2).max(c.2))
+}
+
+/// Reduces the buffer by the given scale in both axes, averaging each block of `scale`x`scale` pixels into one
+pub fn downsample(buffer: &[[u8; 4]], width: usize, scale: usize) -> Vec<[u8; 4]> {
+	let (output_width, output_height) = (width / scale, buffer.len() / width / scale);
+	let samples = (scale * scale) as u32;
+	let mut output = Vec::with_capacity(output_width * output_height);
+	for y in 0..output_height {
+		for x in 0..output_width {
+			let mut sum = [0u32; 4];
+			for dy in 0..scale {
+				for dx in 0..scale {
+					let color = buffer[buffer_index(x * scale + dx, y * scale + dy, width)];
+					for (total, channel) in sum.iter_mut().zip(color) {
+						*total += channel as u32;
+					}
+				}
+			}
+			output.push(sum.map(|total| ((total + samples / 2) / samples) as u8));
+		}
+	}
+	output
+}
+
+/// Mixes two colors channel by channel, from `from` with `t` at `0.0` to `to` with `t` at `1.0`
+#[inline]
+pub fn lerp_color(from: [u8; 4], to: [u8; 4], t: f32) -> [u8; 4] {
+	let mut color = [0; 4];
+	for (i, channel) in color.iter_mut().enumerate() {
+		*channel = (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8;
+	}
+	color
+}
+
+/// Returns the smallest bounds containing both bounds
+#[inline]
+pub fn merge_bounds((ax0, ay0, ax1, ay1): Bounds, (bx0, by0, bx1, by1): Bounds) -> Bounds {
+	(ax0.min(bx0), ay0.min(by0), ax1.max(bx1), ay1.max(by1))
+}
+
+/// Resolves the triangles of a mesh from the list of vertices and the triples of indices, failing if any index
+/// doesn't belong to a vertex
+pub fn mesh_triangles(vertices: &[Position], indices: &[(usize, usize, usize)]) -> Result<Vec<(Position, Position, Position)>, ViewportError> {
+	let vertex = |index: usize| vertices.get(index).copied().ok_or(ViewportError::IndexOutOfBounds { index, len: vertices.len() });
+	indices.iter().map(|&(a, b, c)| Ok((vertex(a)?, vertex(b)?, vertex(c)?))).collect()
+}
+
 #[cfg(test)]
 macro_rules! converts_to {
 	($from:tt -> $to:tt) => {
-		assert_eq!($to, to_pixel($from, (640, 480, 100)));	
+		assert_eq!($to, to_pixel($from, (640, 480, 100), PixelMapping::default()));	
 	};
 }
 
@@ -61,10 +460,97 @@ macro_rules! is_indexed_in {
 fn to_pixel_test() {
 	converts_to!((-1.0, -1.0, -1.0) -> (0, 0, 0));							// Mininum
 	converts_to!((0.0, 0.0, 0.0)    -> (320, 240, 50));					// Middle
-	converts_to!((1.0, 1.0, 1.0)    -> (640, 480, 100));					// Maximum
+	converts_to!((1.0, 1.0, 1.0)    -> (639, 479, 100));					// Maximum
 	converts_to!((-0.25, 0.2, 0.6)  -> (240, 288, 80));							// Random
 }
 
+#[test]
+fn to_pixel_y_up_test() {
+	let y_up = PixelMapping { y_up: true, ..Default::default() };
+	assert_eq!((0, 479, 0), to_pixel((-1.0, -1.0, -1.0), (640, 480, 100), y_up));
+	assert_eq!((320, 239, 50), to_pixel((0.0, 0.0, 0.0), (640, 480, 100), y_up));
+	assert_eq!((240, 191, 80), to_pixel((-0.25, 0.2, 0.6), (640, 480, 100), y_up));
+	assert_eq!((639, 0, 100), to_pixel((1.0, 1.0, 1.0), (640, 480, 100), y_up));
+}
+
+#[test]
+fn to_pixel_clamp_test() {
+	let clamp = PixelMapping { clamp: true, ..Default::default() };
+	assert_eq!((0, 0, 0), to_pixel((-2.0, -1.5, -1.0), (640, 480, 100), clamp));
+	assert_eq!((639, 479, 100), to_pixel((1.0, 3.0, 1.0), (640, 480, 100), clamp));
+	assert_eq!((320, 240, 50), to_pixel((0.0, 0.0, 0.0), (640, 480, 100), clamp));
+	let both = PixelMapping { clamp: true, y_up: true, ..Default::default() };
+	assert_eq!((639, 0, 100), to_pixel((1.0, 1.0, 1.0), (640, 480, 100), both));
+}
+
+#[test]
+fn to_pixel_unit_top_left_test() {
+	let unit = PixelMapping { coord_space: CoordSpace::UnitTopLeft, ..Default::default() };
+	assert_eq!((0, 0, 0), to_pixel((0.0, 0.0, -1.0), (640, 480, 100), unit));
+	assert_eq!((320, 240, 50), to_pixel((0.5, 0.5, 0.0), (640, 480, 100), unit));
+	assert_eq!((160, 384, 80), to_pixel((0.25, 0.8, 0.6), (640, 480, 100), unit));
+	let y_up = PixelMapping { y_up: true, ..unit };
+	assert_eq!((0, 479, 0), to_pixel((0.0, 0.0, -1.0), (640, 480, 100), y_up));
+}
+
+#[test]
+fn to_pixel_f64_test() {
+	let positions = [(-1.0, -1.0, -1.0), (-0.25, 0.2, 0.6), (0.3, -0.7, 0.1), (0.999, 0.5, -0.33)];
+	for (x, y, z) in positions {
+		let precise = to_pixel((x, y, z), (640, 480, 100), PixelMapping::default());
+		let single = to_pixel((x as f32, y as f32, z as f32), (640, 480, 100), PixelMapping::default());
+		assert_eq!(precise, single);
+	}
+	let (x, y, _) = to_pixel((0.5f64, 0.5, 0.0), (1 << 26, 1 << 26, 1), PixelMapping::default());
+	let (next, _, _) = to_pixel((0.5f64 + 1e-7, 0.5, 0.0), (1 << 26, 1 << 26, 1), PixelMapping::default());
+	assert_eq!((x, y), (3 << 24, 3 << 24));
+	assert!(next > x);
+}
+
+#[test]
+fn to_normalized_test() {
+	assert_eq!(to_normalized((0.0, 0.0), (4, 2), PixelMapping::default()), (-0.75, -0.5));
+	assert_eq!(to_normalized((3.0, 1.0), (4, 2), PixelMapping::default()), (0.75, 0.5));
+	let y_up = PixelMapping { y_up: true, ..Default::default() };
+	assert_eq!(to_normalized((0.0, 0.0), (4, 2), y_up), (-0.75, 0.5));
+	let unit = PixelMapping { coord_space: CoordSpace::UnitTopLeft, ..Default::default() };
+	assert_eq!(to_normalized((1.0, 1.0), (4, 2), unit), (0.375, 0.75));
+
+	for mapping in [PixelMapping::default(), y_up, unit, PixelMapping { y_up: true, ..unit }] {
+		for (x, y) in [(0, 0), (639, 479), (320, 240), (17, 401)] {
+			let (nx, ny) = to_normalized((x as f64, y as f64), (640, 480), mapping);
+			assert_eq!(to_pixel((nx, ny, 0.0), (640, 480, 100), mapping), (x, y, 50));
+		}
+	}
+}
+
+#[test]
+fn to_depth_test() {
+	assert_eq!(to_depth(-1.0, 100), 0);
+	assert_eq!(to_depth(0.0, 100), 50);
+	assert_eq!(to_depth(1.0, 100), 100);
+	assert_eq!(to_depth(0.6, 100), 80);
+	assert_eq!(to_depth(-2.0, 100), 0);
+}
+
+#[test]
+fn clip_z_test() {
+	assert_eq!(clip_z((0.0, 0.0, -0.5), (1.0, 1.0, 0.5)), Some(((0.0, 0.0, -0.5), (1.0, 1.0, 0.5))));
+	assert_eq!(clip_z((-1.0, -1.0, -2.0), (0.5, 0.5, 0.0)), Some(((-0.25, -0.25, -1.0), (0.5, 0.5, 0.0))));
+	assert_eq!(clip_z((0.0, 0.0, 0.0), (1.0, -1.0, 2.0)), Some(((0.0, 0.0, 0.0), (0.5, -0.5, 1.0))));
+	assert_eq!(clip_z((0.0, 0.0, -3.0), (0.0, 0.0, 3.0)), Some(((0.0, 0.0, -1.0), (0.0, 0.0, 1.0))));
+	assert_eq!(clip_z((0.0, 0.0, 1.5), (1.0, 0.0, 1.5)), None);
+	assert_eq!(clip_z((0.0, 0.0, -1.5), (1.0, 0.0, -3.0)), None);
+}
+
+#[test]
+fn line_voxels_test() {
+	assert_eq!(line_voxels((0, 0, 0), (4, 0, 3)).collect::<Vec<_>>(), vec![(0, 0, 0), (1, 0, 1), (2, 0, 2), (3, 0, 2), (4, 0, 3)]);
+	assert_eq!(line_voxels((0, 4, 10), (0, 0, 0)).collect::<Vec<_>>(), vec![(0, 0, 0), (0, 1, 3), (0, 2, 5), (0, 3, 8), (0, 4, 10)]);
+	assert_eq!(line_voxels((9, 2, 7), (1, 5, 0)).collect::<Vec<_>>(), line_voxels((1, 5, 0), (9, 2, 7)).collect::<Vec<_>>());
+	assert_eq!(line_voxels((3, 3, -2), (3, 3, 2)).collect::<Vec<_>>(), vec![(3, 3, 2)]);
+}
+
 #[test]
 fn buffer_index_test() {
 	is_indexed_in!(0, 0 with 640 width has index 0);
@@ -80,8 +566,187 @@ fn sort_vectors_test() {
 	assert_eq!(((0, 10, 5), (10, 5, 0), (5, 0, 10)), sort_vectors((5, 0, 10), (10, 5, 0), (0, 10, 5)));
 }
 
+#[test]
+fn scanline_spans_test() {
+	let rows = |spans: Vec<(Voxel<isize>, Voxel<isize>)>| {
+		let mut rows = std::collections::BTreeMap::new();
+		for (a, b) in spans {
+			let row = rows.entry(a.1).or_insert((a.0, a.0));
+			*row = (row.0.min(a.0).min(b.0), row.1.max(a.0).max(b.0));
+		}
+		rows.into_iter().map(|(y, (left, right))| (y, left, right)).collect::<Vec<_>>()
+	};
+	for (a, b, c) in [
+		((0, 0, 0), (4, 0, 0), (0, 4, 0)),
+		((0, 0, 0), (8, 8, 0), (0, 8, 0)),
+		((0, 0, 0), (6, 6, 0), (6, 0, 0)),
+		((0, 0, 2), (5, 0, 2), (5, 5, 2)),
+	] {
+		assert_eq!(rows(scanline_spans(a, b, c)), rows(triangle_spans(a, b, c)));
+	}
+	for (a, b, c) in [
+		((4, 0, 0), (0, 8, 0), (8, 8, 0)),
+		((0, 0, 0), (8, 4, 0), (0, 8, 0)),
+		((0, 0, 0), (2, 8, 0), (6, 3, 0)),
+	] {
+		let (new, old) = (rows(scanline_spans(a, b, c)), rows(triangle_spans(a, b, c)));
+		assert_eq!(new.len(), old.len());
+		for ((new_y, new_left, new_right), (old_y, old_left, old_right)) in new.into_iter().zip(old) {
+			assert_eq!(new_y, old_y);
+			assert!((new_left - old_left).abs() <= 1);
+			assert!((new_right - old_right).abs() <= 1);
+		}
+	}
+	assert_eq!(scanline_spans((0, 0, 0), (2, 2, 0), (4, 4, 0)).len(), 5);
+	assert_eq!(scanline_spans((0, 3, 0), (4, 3, 8), (2, 3, 4)), vec![((0, 3, 0), (4, 3, 8))]);
+}
+
+#[test]
+fn triangle_spans_test() {
+	let spans = triangle_spans((0, 0, 0), (4, 0, 0), (0, 4, 0));
+	assert_eq!(spans.len(), 5);
+	assert!(spans.contains(&((4, 0, 0), (0, 0, 0))));
+	assert!(spans.contains(&((0, 4, 0), (0, 4, 0))));
+	assert_eq!(triangle_spans((0, 0, 0), (2, 2, 0), (4, 4, 0)).len(), 3);
+}
+
 #[test]
 fn calculate_intersection_test() {
 	assert_eq!(calculate_intersection((4, 0, 2), (0, 2, 1), (0, 4, 4)), (2, 2, 3));
 	assert_eq!(calculate_intersection((4, 0, 2), (0, 2, 1), (8, 4, -2)), (6, 2, 0));
 }
+
+#[test]
+fn barycentric_test() {
+	let (a, b, c) = ((0, 0, 0), (6, 0, 0), (0, 6, 0));
+	assert_eq!(barycentric(a, b, c, (0, 0)), Some((1.0, 0.0, 0.0)));
+	assert_eq!(barycentric(a, b, c, (2, 2)), Some((1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0)));
+	assert_eq!(barycentric(a, c, b, (0, 3)), Some((0.5, 0.5, 0.0)));
+	assert_eq!(barycentric(a, b, c, (4, 4)), None);
+	assert_eq!(barycentric(a, b, (3, 0, 0), (1, 0)), None);
+}
+
+#[test]
+fn barycentric_weights_test() {
+	let (a, b, c) = ((0, 0, 0), (6, 0, 0), (0, 6, 0));
+	assert_eq!(barycentric_weights(a, b, c, (2, 2)), Some((1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0)));
+	assert_eq!(barycentric_weights(a, b, c, (6, 6)), Some((-1.0, 1.0, 1.0)));
+	assert_eq!(barycentric_weights(a, b, (3, 0, 0), (1, 0)), None);
+}
+
+#[test]
+fn edge_distance_test() {
+	let (a, b, c) = ((0, 0, 0), (6, 0, 0), (0, 6, 0));
+	assert_eq!(edge_distance(a, b, c, (1, 2)), 1.0);
+	assert_eq!(edge_distance(a, c, b, (1, 2)), 1.0);
+	assert_eq!(edge_distance(a, b, c, (3, 0)), 0.0);
+	assert_eq!(edge_distance(a, b, c, (3, -2)), -2.0);
+	assert!((edge_distance(a, b, c, (4, 4)) + 2.0f32.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn plane_depth_test() {
+	let (a, b, c) = ((0, 0, 0), (6, 0, 6), (0, 6, 0));
+	assert_eq!(plane_depth(a, b, c, (3, 1)), 3);
+	assert_eq!(plane_depth(a, c, b, (2, 2)), 2);
+	assert_eq!(plane_depth(a, b, c, (9, 0)), 6);
+	assert_eq!(plane_depth(a, b, c, (-2, 0)), 0);
+}
+
+#[test]
+fn downsample_test() {
+	let buffer = [
+		[255, 0, 0, 255], [0, 0, 0, 255], [8, 8, 8, 8], [8, 8, 8, 8],
+		[255, 0, 0, 255], [0, 0, 0, 255], [8, 8, 8, 8], [0, 0, 0, 0],
+	];
+	assert_eq!(downsample(&buffer, 4, 2), vec![[128, 0, 0, 255], [6, 6, 6, 6]]);
+	assert_eq!(downsample(&buffer, 4, 1), buffer.to_vec());
+}
+
+#[test]
+fn is_degenerate_test() {
+	assert!(is_degenerate((0, 0, 0), (2, 2, 1), (4, 4, 2)));
+	assert!(is_degenerate((0, 3, 0), (5, 3, 0), (2, 3, 8)));
+	assert!(is_degenerate((1, 1, 0), (1, 1, 4), (6, 2, 0)));
+	assert!(!is_degenerate((0, 0, 0), (4, 0, 0), (0, 4, 0)));
+}
+
+#[test]
+fn is_self_intersecting_test() {
+	let (a, b, c, d) = ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (0.0, 1.0, 0.0));
+	assert!(!is_self_intersecting(a, b, c, d));
+	assert!(!is_self_intersecting(d, c, b, a));
+	assert!(is_self_intersecting(a, c, b, d));
+	assert!(is_self_intersecting(a, b, d, c));
+}
+
+#[test]
+fn circle_octant_test() {
+	assert_eq!(circle_octant(0), vec![(0, 0)]);
+	assert_eq!(circle_octant(4), vec![(4, 0), (4, 1), (3, 2), (3, 3)]);
+}
+
+#[test]
+fn edge_table_test() {
+	let square = [(0, 0, 0), (4, 0, 0), (4, 4, 2), (0, 4, 2)];
+	assert_eq!(edge_table(&square), vec![((4, 0, 0), (4, 4, 2)), ((0, 0, 0), (0, 4, 2))]);
+}
+
+#[test]
+fn edge_crossing_test() {
+	assert_eq!(edge_crossing((0, 0, 0), (4, 4, 2), 2), (2, 1));
+	assert_eq!(edge_crossing((4, 0, 8), (0, 8, 0), 6), (1, 2));
+}
+
+#[test]
+fn ellipse_quadrant_test() {
+	assert_eq!(ellipse_quadrant(0, 0), vec![(0, 0)]);
+	assert_eq!(ellipse_quadrant(0, 2), vec![(0, 0), (0, 1), (0, 2)]);
+	assert_eq!(ellipse_quadrant(2, 0), vec![(0, 0), (1, 0), (2, 0)]);
+	assert_eq!(ellipse_quadrant(4, 2), vec![(0, 2), (1, 2), (2, 2), (3, 1), (4, 0)]);
+}
+
+#[test]
+fn bezier_point_test() {
+	let controls = [(0.0, 0.0, 0.0), (0.5, 1.0, 0.5), (1.0, 0.0, 1.0)];
+	assert_eq!(bezier_point(&controls, 0.0), (0.0, 0.0, 0.0));
+	assert_eq!(bezier_point(&controls, 0.5), (0.5, 0.5, 0.5));
+	assert_eq!(bezier_point(&controls, 1.0), (1.0, 0.0, 1.0));
+	let controls = [(0.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0), (1.0, 0.0, 0.0)];
+	assert_eq!(bezier_point(&controls, 0.5), (0.5, 0.75, 0.0));
+}
+
+#[test]
+fn catmull_rom_point_test() {
+	let points = [(0.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 0.0, 1.0), (3.0, 1.0, 1.0)];
+	assert_eq!(catmull_rom_point(points[0], points[1], points[2], points[3], 0.0), points[1]);
+	assert_eq!(catmull_rom_point(points[0], points[1], points[2], points[3], 1.0), points[2]);
+	assert_eq!(catmull_rom_point(points[0], points[1], points[2], points[3], 0.5), (1.5, 0.5, 0.5));
+}
+
+#[test]
+fn mesh_triangles_test() {
+	let vertices = [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0)];
+	assert_eq!(mesh_triangles(&vertices, &[(0, 1, 2)]).unwrap(), vec![(vertices[0], vertices[1], vertices[2])]);
+	assert!(matches!(mesh_triangles(&vertices, &[(0, 1, 2), (2, 3, 0)]), Err(ViewportError::IndexOutOfBounds { index: 3, len: 3 })));
+}
+
+#[test]
+fn row_depth_test() {
+	assert_eq!(row_depth(4, (4, 10), (4, 20)), 10);
+	assert_eq!(row_depth(2, (0, 10), (4, 20)), 15);
+	assert_eq!(row_depth(4, (0, 20), (4, 10)), 10);
+}
+
+#[test]
+fn lerp_color_test() {
+	assert_eq!(lerp_color([0, 0, 0, 255], [255, 100, 10, 255], 0.0), [0, 0, 0, 255]);
+	assert_eq!(lerp_color([0, 0, 0, 255], [255, 100, 10, 255], 1.0), [255, 100, 10, 255]);
+	assert_eq!(lerp_color([0, 200, 0, 0], [255, 100, 10, 255], 0.5), [128, 150, 5, 128]);
+}
+
+#[test]
+fn merge_bounds_test() {
+	assert_eq!(merge_bounds((2, 3, 4, 5), (3, 3, 3, 3)), (2, 3, 4, 5));
+	assert_eq!(merge_bounds((2, 3, 4, 5), (0, 8, 1, 9)), (0, 3, 4, 9));
+}
@@ -3,17 +3,40 @@
 mod factory;
 pub use factory::ViewportFactory;
 
+/// Length in pixels of the control polygon covered by each of the segments used to draw a Bézier curve
+const BEZIER_SEGMENT_LENGTH: f32 = 4.0;
+
 /// [Viewport] for rendering with `winit`
 pub type WinitViewport<S> = Viewport<S, crate::render::WinitRenderer>; 
 
+/// [Viewport] for rendering into memory, without any window, into frames of `N` channels
+pub type HeadlessViewport<S, const N: usize = 4> = Viewport<S, crate::render::MemoryRenderer<N>>;
+
+/// [Viewport] for rendering into a canvas of the browser
+#[cfg(feature = "web")]
+pub type WebViewport<S> = Viewport<S, crate::render::WebRenderer>;
+
+use crate::blend::BlendMode;
+use crate::color::{map_color, Color};
 use crate::error::ViewportError;
-use crate::pixel::Pixel;
 use crate::render::{Render, Resize};
-use crate::util::{as_signed, buffer_index, calculate_intersection, sort_vectors, to_pixel};
-use crate::{PixelSize, Position, Voxel};
+use crate::time::FrameClock;
+use crate::transform::Transform2D;
+use crate::util::{
+    as_signed, barycentric, barycentric_weights, bezier_point, buffer_index, calculate_intersection, catmull_rom_point, circle_octant,
+    clip_z, downsample, edge_crossing, edge_distance, edge_table, ellipse_quadrant, is_degenerate,
+    is_self_intersecting, lerp_color, line_voxels, merge_bounds, mesh_triangles, plane_depth, px_to_voxel,
+    row_depth, scanline_spans, sort_vectors, to_depth, to_f64, to_normalized, to_pixel, triangle_spans, Bounds,
+    PixelMapping,
+};
+use crate::{Coord, PixelSize, Position, Voxel};
 use bresenham_zip::build_zip;
-use line_drawing::Bresenham3d;
-use log::info;
+use log::{info, warn};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::f32::consts::TAU;
+use winit::event::WindowEvent;
 
 /// Entity in charge of offering the functions to draw on the screen and handle to logic of the operation.
 /// It works using three-dimensional normalized vectors of type (x: f32, y: f32, z: f32).
@@ -22,18 +45,112 @@ use log::info;
 /// The `z` value works as a layer function, it will draw only the point with the highest `z` on the same translated pixel.
 ///
 /// **The viewport doesn't perform projection**, that should be handled by the user before calling the functions.
+/// The [`Camera`](crate::camera::Camera) can be used to project the world coordinates.
 /// Viewport just draws the pixels of the highest depth relative to the given coordinates.
 ///
 /// The axes directions are:
 /// * `x`: west -> east
-/// * `y`: north -> south
+/// * `y`: north -> south, or south -> north using [`Viewport::set_y_up`]
 /// * `z`: far -> near
 pub struct Viewport<S, R> {
     width: S,
     height: S,
     depth: S,
-    buffer: Vec<Pixel>,
+    buffer: Vec<[u8; 4]>,
+    depth_buffer: Vec<usize>,
+    stencil: Vec<bool>,
     renderer: R,
+    color_lut: Option<[[u8; 256]; 3]>,
+    gamma: f32,
+    output_lut: Option<[[u8; 256]; 3]>,
+    premultiplied: bool,
+    fog: Option<([u8; 4], f32, f32)>,
+    antialiasing: bool,
+    blend: BlendMode,
+    background: [u8; 4],
+    mapping: PixelMapping,
+    dirty: Option<Bounds>,
+    cleared: bool,
+    rendered_hash: Option<u64>,
+    transforms: Vec<Transform2D>,
+    clip: Option<Bounds>,
+    scale: usize,
+    clock: Option<FrameClock>,
+    cull: CullMode,
+    polygon_mode: PolygonMode,
+    stencil_recording: bool,
+    stencil_test: bool,
+    depth_test: bool,
+    bounds_policy: BoundsPolicy,
+    out_of_bounds: Option<(isize, isize)>,
+}
+
+/// Range of the `x` and `y` coordinates covering the screen of a [Viewport], set with [`Viewport::set_coord_space`].
+/// The `z` coordinate always goes from `-1.0` to `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoordSpace {
+    /// From `-1.0` to `1.0`, with `(0.0, 0.0)` at the center of the screen.
+    #[default]
+    CenteredNormalized,
+    /// From `0.0` to `1.0`, with `(0.0, 0.0)` at the top left corner of the screen.
+    UnitTopLeft,
+}
+
+/// Winding of the triangles to skip, set with [`Viewport::set_cull_mode`]. The winding is the order of the points
+/// of the triangle as seen on the screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CullMode {
+    /// All the triangles are drawn.
+    #[default]
+    None,
+    /// The triangles with their points in clockwise order are skipped.
+    Clockwise,
+    /// The triangles with their points in counterclockwise order are skipped.
+    CounterClockwise,
+}
+
+/// Way to draw the filled shapes, set with [`Viewport::set_polygon_mode`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PolygonMode {
+    /// The shapes are filled.
+    #[default]
+    Fill,
+    /// Only the edges of the shapes are drawn.
+    Line,
+    /// Only the vertices of the shapes are drawn.
+    Point,
+}
+
+/// Way to handle the pixels drawn out of the screen, set with [`Viewport::set_bounds_policy`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoundsPolicy {
+    /// The pixels out of the screen are ignored.
+    #[default]
+    Discard,
+    /// The coordinates out of the screen are moved to the closest edge, like with [`Viewport::set_clamp`].
+    Clamp,
+    /// The pixels out of the screen are ignored and the next render fails with [`ViewportError::OutOfBounds`].
+    Error,
+}
+
+/// Direction of the color change of [`Viewport::fill_rectangle_gradient`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GradientDir {
+    /// From the left column to the right one.
+    #[default]
+    Horizontal,
+    /// From the top row to the bottom one.
+    Vertical,
+}
+
+/// Saved state of the buffer of a [Viewport], created with [`Viewport::snapshot`] to restore it later
+/// with [`Viewport::restore`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BufferSnapshot {
+    width: u32,
+    height: u32,
+    buffer: Vec<[u8; 4]>,
+    depth_buffer: Vec<usize>,
 }
 
 impl<S: PixelSize, R> Viewport<S, R> {
@@ -56,17 +173,59 @@ impl<S: PixelSize, R> Viewport<S, R> {
             width,
             height,
             depth,
-            buffer: vec![Pixel::default(); buffer_size],
+            buffer: vec![[0, 0, 0, 0]; buffer_size],
+            depth_buffer: vec![usize::MIN; buffer_size],
+            stencil: vec![false; buffer_size],
             renderer,
+            color_lut: None,
+            gamma: 1.0,
+            output_lut: None,
+            premultiplied: false,
+            fog: None,
+            antialiasing: false,
+            blend: BlendMode::default(),
+            background: [0, 0, 0, 0],
+            mapping: PixelMapping::default(),
+            dirty: None,
+            cleared: true,
+            rendered_hash: None,
+            transforms: Vec::new(),
+            clip: None,
+            scale: 1,
+            clock: None,
+            cull: CullMode::None,
+            polygon_mode: PolygonMode::Fill,
+            stencil_recording: false,
+            stencil_test: false,
+            depth_test: true,
+            bounds_policy: BoundsPolicy::Discard,
+            out_of_bounds: None,
         }
     }
 
-    /// Returns the width of the current window
+    /// Builds a new Viewport drawing in a buffer larger than the screen by the given scale. The buffer is
+    /// downsampled to the size of the screen when rendering.
+    ///
+    /// # Arguments
+    /// * `width`. Width in pixels of the screen, must be an unsigned value.
+    /// * `height`. Height in pixels of the screen, must be an unsigned value.
+    /// * `depth`. Depth to assume in the `z` axis calculations, must be an unsigned value.
+    /// * `scale`. Number of pixels of the buffer per pixel of the screen in each axis, must be greater than zero.
+    /// * `renderer`: Renderer to draw on
+    ///
+    pub(crate) fn new_scaled(width: S, height: S, depth: S, scale: usize, renderer: R) -> Self {
+        assert!(scale > 0);
+        let mut viewport = Self::new(width * S::cast(scale), height * S::cast(scale), depth, renderer);
+        viewport.scale = scale;
+        viewport
+    }
+
+    /// Returns the width of the current buffer, the one of the window multiplied by the render scale
     pub fn width(&self) -> S {
         self.width
     }
 
-    /// Returns the height of the current window
+    /// Returns the height of the current buffer, the one of the window multiplied by the render scale
     pub fn height(&self) -> S {
         self.height
     }
@@ -76,38 +235,21 @@ impl<S: PixelSize, R> Viewport<S, R> {
         self.depth
     }
 
-    /// Returns the sizes of the viewport in usize to use in the pixels calculation
-    fn sizes(&self) -> (usize, usize, usize) {
-        (
-            usize::cast(self.width),
-            usize::cast(self.height),
-            usize::cast(self.depth),
-        )
-    }
-
-    /// Adds a pixel to the buffer. It also verifies the color array and throws a panic if it's not correct.
-    fn push_pixel<'a>(&mut self, (x, y, z): Voxel<usize>, color: &'a [u8]) {
-        assert_eq!(4, color.len());
-        let i = buffer_index(x, y, usize::cast(self.width));
-        if i < self.buffer.len() && z >= self.buffer[i].depth {
-            self.buffer[i] = Pixel::new(color, z);
-        }
+    /// Returns the width of the current buffer in pixels
+    pub fn width_px(&self) -> u32 {
+        u32::cast(self.width)
     }
 
-    /// Adds the pixels between two points to the buffer using the `push_pixel` function.
-    fn push_line<'a>(&mut self, start: Voxel<isize>, end: Voxel<isize>, color: &'a [u8]) {
-        for (x, y, z) in Bresenham3d::new(start, end) {
-            self.push_pixel((x as usize, y as usize, z as usize), color);
-        }
+    /// Returns the height of the current buffer in pixels
+    pub fn height_px(&self) -> u32 {
+        u32::cast(self.height)
     }
 
-    /// Commands the drawing of a point in the window. It will be rendered in the next call to [`Viewport::render`].
-    /// If two drawn points fall on the same pixel, the point with the lowest `z` will be ignored.
+    /// Enables or disables the [FrameClock] updated on each call to [`Viewport::render`] and
+    /// [`Viewport::render_dirty`]. It's disabled by default.
     ///
     /// # Arguments
-    /// * `position`, coordinates of the point in `(f32, f32, f32)`.
-    /// * `color`, color of the point to draw. It should be provided as raw RGB values, alpha is included,
-    /// so the expectation is a &[u8; 4] color like `&[255, 0, 0, 255]` for red with 100% opacity.
+    /// * `enabled`, true to track the time between renders, false to stop tracking it.
     ///
     /// # Example
     /// ```no_run
@@ -116,27 +258,32 @@ impl<S: PixelSize, R> Viewport<S, R> {
     /// # let event_loop = winit::event_loop::EventLoop::new();
     /// # let window = winit::window::Window::new(&event_loop).unwrap();
     /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.draw_point((0.0, 0.0, 0.0), &[255, 255, 255, 255]); // white point in the center of the screen
-    /// viewport.render()?; // renders the point in the window
+    /// viewport.set_frame_clock(true);
+    /// viewport.render()?;
+    /// let delta = viewport.frame_clock().map_or(0.0, |clock| clock.delta_seconds());
 	/// # Ok (())
 	/// # }
     /// ```
-    ///
-    /// # Panic
-    /// Passing a color with the wrong number of members will throw a panic. It's required to have length four (R, G, B, A);
-    ///
-    pub fn draw_point<'a>(&mut self, position: Position, color: &'a [u8]) {
-        let voxel = to_pixel(position, self.sizes());
-        self.push_pixel(voxel, color);
+    pub fn set_frame_clock(&mut self, enabled: bool) {
+        self.clock = enabled.then(|| self.clock.unwrap_or_default());
     }
 
-    /// Commands the drawing of a line in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Returns the clock tracking the time between renders, if enabled with [`Viewport::set_frame_clock`]
+    pub fn frame_clock(&self) -> Option<&FrameClock> {
+        self.clock.as_ref()
+    }
+
+    /// Returns the renderer of the viewport
+    pub fn renderer(&self) -> &R {
+        &self.renderer
+    }
+
+    /// Sets the color lookup table to apply to the output when rendering, or `None` to remove it.
+    /// Each channel (R, G, B) is mapped through its own 256-entry table, the alpha is left untouched.
+    /// The buffer is not modified, so the table can be changed or removed between frames.
     ///
     /// # Arguments
-    /// * `start`, coordinates of the starting point of the line.
-    /// * `end`, coordinates of the ending point of the line.
-    /// * `color`, color of the line to draw. It should be provided as raw RGB values, alpha is included,
-    /// so the expectation is a &[u8; 4] color like `&[255, 0, 0, 255]` for red with 100% opacity.
+    /// * `lut`, tables for the red, green and blue channels, in that order.
     ///
     /// # Example
     /// ```no_run
@@ -145,62 +292,144 @@ impl<S: PixelSize, R> Viewport<S, R> {
     /// # let event_loop = winit::event_loop::EventLoop::new();
     /// # let window = winit::window::Window::new(&event_loop).unwrap();
     /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.draw_line((-0.5, -0.5, -0.5), (0.25, 0.5, 0.0), &[255, 255, 255, 255]);
-    /// viewport.render()?; // renders the line in the window
+    /// let mut invert = [0; 256];
+    /// for (i, value) in invert.iter_mut().enumerate() {
+    ///     *value = 255 - i as u8;
+    /// }
+    /// viewport.set_color_lut(Some([invert; 3]));
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders a black point
 	/// # Ok (())
 	/// # }
     /// ```
+    pub fn set_color_lut(&mut self, lut: Option<[[u8; 256]; 3]>) {
+        self.color_lut = lut;
+        self.update_output_lut();
+    }
+
+    /// Sets the gamma to apply to the output when rendering. Each channel (R, G, B) is raised to `1 / gamma`,
+    /// encoding the linearly blended colors for the display, after the color lookup table if there's one.
+    /// The default gamma is `1.0`, which leaves the output untouched, and the buffer is never modified.
     ///
-    /// # Panic
-    /// Passing a color with the wrong number of members will throw a panic. It's required to have length four (R, G, B, A);
+    /// # Arguments
+    /// * `gamma`, gamma of the display, usually `2.2`. Non-positive values are ignored.
     ///
-    pub fn draw_line<'a>(&mut self, start: Position, end: Position, color: &'a [u8]) {
-        let start = to_pixel(start, self.sizes());
-        let end = to_pixel(end, self.sizes());
-        self.push_line(as_signed(start), as_signed(end), color);
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_gamma(2.2);
+    /// viewport.draw_point_px((0, 0, 0), [128, 128, 128, 255]);
+    /// viewport.render().unwrap();
+    /// assert_eq!(viewport.renderer().frame()[0..4], [186, 186, 186, 255]);
+    /// ```
+    pub fn set_gamma(&mut self, gamma: f32) {
+        if !(gamma > 0.0 && gamma.is_finite()) {
+            warn!("Ignoring invalid gamma {gamma}");
+            return;
+        }
+        self.gamma = gamma;
+        self.update_output_lut();
     }
 
-    /// Commands the drawing of a triangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Combines the color lookup table and the gamma in the table applied to the output
+    fn update_output_lut(&mut self) {
+        if self.gamma == 1.0 {
+            self.output_lut = self.color_lut;
+            return;
+        }
+        let mut gamma = [0; 256];
+        for (i, value) in gamma.iter_mut().enumerate() {
+            *value = ((i as f32 / 255.0).powf(1.0 / self.gamma) * 255.0).round() as u8;
+        }
+        let mut lut = self.color_lut.unwrap_or_else(|| {
+            let mut identity = [0; 256];
+            for (i, value) in identity.iter_mut().enumerate() {
+                *value = i as u8;
+            }
+            [identity; 3]
+        });
+        for value in lut.iter_mut().flatten() {
+            *value = gamma[*value as usize];
+        }
+        self.output_lut = Some(lut);
+    }
+
+    /// Sets if the output has to be rendered with premultiplied alpha, as some compositors expect. When enabled,
+    /// each channel (R, G, B) is multiplied by the alpha of the pixel after the color lookup table and the gamma.
+    /// It's disabled by default and the buffer is never modified.
     ///
     /// # Arguments
-    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
-    /// * `color`, color of the line to draw. It should be provided as raw RGB values, alpha is included,
-    /// so the expectation is a &[u8; 4] color like `&[255, 0, 0, 255]` for red with 100% opacity.
+    /// * `enabled`, if the alpha has to be premultiplied.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_premultiplied(true);
+    /// viewport.draw_point_px((0, 0, 0), [255, 0, 100, 51]);
+    /// viewport.render().unwrap();
+    /// assert_eq!(viewport.renderer().frame()[0..4], [51, 0, 20, 51]);
+    /// ```
+    pub fn set_premultiplied(&mut self, enabled: bool) {
+        self.premultiplied = enabled;
+    }
+
+    /// Sets a fog fading the pixels towards the given color the further they are, based on the depth stored for
+    /// each one of them. It's applied on render before the color lookup table, and the buffer is never modified.
+    /// The pixels never drawn are at the furthest depth, so they take the fog color. It's disabled by default.
+    ///
+    /// # Arguments
+    /// * `color`, color of the fog. It should usually match the background.
+    /// * `near`, `z` coordinate where the fog starts. The pixels closer than it keep their color.
+    /// * `far`, `z` coordinate where the fog is complete. The pixels further than it take the fog color.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_fog([255, 255, 255, 255], 0.0, -1.0);
+    /// viewport.draw_point_px((0, 0, 25), [0, 0, 0, 255]);
+    /// viewport.render().unwrap();
+    /// assert_eq!(viewport.renderer().frame()[0..4], [128, 128, 128, 255]);
+    /// ```
+    pub fn set_fog(&mut self, color: [u8; 4], near: f32, far: f32) {
+        self.fog = Some((color, near, far));
+    }
+
+    /// Removes the fog set with [`Viewport::set_fog`]
+    pub fn clear_fog(&mut self) {
+        self.fog = None;
+    }
+
+    /// Sets the mode to mix the drawn colors with the ones already in the buffer. Only the pixels passing
+    /// the depth test are mixed. The default mode is [`BlendMode::Replace`].
+    ///
+    /// # Arguments
+    /// * `blend`, mode to use in the following drawings.
     ///
     /// # Example
     /// ```no_run
 	/// # use std::error::Error;
+	/// # use ferrux_viewport::blend::BlendMode;
 	/// # fn main() -> Result<(), Box<dyn Error>> {
     /// # let event_loop = winit::event_loop::EventLoop::new();
     /// # let window = winit::window::Window::new(&event_loop).unwrap();
     /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.draw_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), &[255, 255, 255, 255]);
-    /// viewport.render()?; // renders the triangle in the window
+    /// viewport.set_blend(BlendMode::AlphaOver);
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [0, 0, 255]);
+    /// viewport.fill_triangle((0.0, 0.0, 0.0), (-0.5, 0.5, 0.5), (0.5, 0.5, 0.5), [255, 0, 0, 128]);
+    /// viewport.render()?; // renders a purple triangle
 	/// # Ok (())
 	/// # }
     /// ```
-    ///
-    /// # Panic
-    /// Passing a color with the wrong number of members will throw a panic. It's required to have length four (R, G, B, A);
-    ///
-    pub fn draw_triangle<'a>(
-        &mut self,
-        point_a: Position,
-        point_b: Position,
-        point_c: Position,
-        color: &'a [u8],
-    ) {
-        self.draw_line(point_a, point_b, color);
-        self.draw_line(point_b, point_c, color);
-        self.draw_line(point_c, point_a, color);
+    pub fn set_blend(&mut self, blend: BlendMode) {
+        self.blend = blend;
     }
 
-    /// Commands the drawing and filling of a triangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Sets the color of the background. It will be used to fill the buffer in the next call to [`Viewport::reset_buffer`]
+    /// and to draw the empty frames of [`Viewport::clear_frame`], always opaque.
+    /// The default background is `[0, 0, 0, 0]`.
     ///
     /// # Arguments
-    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
-    /// * `color`, color of the line to draw. It should be provided as raw RGB values, alpha is included,
-    /// so the expectation is a &[u8; 4] color like `&[255, 0, 0, 255]` for red with 100% opacity.
+    /// * `color`, color of the background in RGBA.
     ///
     /// # Example
     /// ```no_run
@@ -209,204 +438,5283 @@ impl<S: PixelSize, R> Viewport<S, R> {
     /// # let event_loop = winit::event_loop::EventLoop::new();
     /// # let window = winit::window::Window::new(&event_loop).unwrap();
     /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), &[255, 255, 255, 255]);
-    /// viewport.render()?; // renders the triangle in the window
+    /// viewport.set_background([255, 255, 255, 255]);
+    /// viewport.reset_buffer();    // fills the buffer with white
+    /// viewport.draw_point((0.0, 0.0, 0.0), [0, 0, 0, 255]);
+    /// viewport.render()?;         // renders a black point over a white background
 	/// # Ok (())
 	/// # }
     /// ```
+    pub fn set_background(&mut self, color: [u8; 4]) {
+        self.background = color;
+    }
+
+    /// Sets the range of the `x` and `y` coordinates covering the screen. By default it's
+    /// [`CoordSpace::CenteredNormalized`], from `-1.0` to `1.0` with the origin at the center, while
+    /// [`CoordSpace::UnitTopLeft`] goes from `0.0` to `1.0` with the origin at the top left corner, handier for
+    /// UI-style code. The radii are measured in the same scale, so a radius of `1.0` covers the whole screen in unit space.
+    /// The functions working directly with pixel coordinates are not affected.
     ///
-    /// # Panic
-    /// Passing a color with the wrong number of members will throw a panic. It's required to have length four (R, G, B, A);
+    /// # Arguments
+    /// * `coord_space`, range of the coordinates to use in the following drawings.
     ///
-    pub fn fill_triangle<'a>(
-        &mut self,
-        point_a: Position,
-        point_b: Position,
-        point_c: Position,
-        color: &'a [u8],
-    ) {
-        let point_a = as_signed(to_pixel(point_a, self.sizes()));
-        let point_b = as_signed(to_pixel(point_b, self.sizes()));
-        let point_c = as_signed(to_pixel(point_c, self.sizes()));
-
-        let (point_a, point_b, point_c) = sort_vectors(point_a, point_b, point_c);
-        match point_b {
-            (_, y, _) if y == point_c.1 => {
-                self.fill_flat_triangle(point_a, point_b, point_c, color)
-            }
-            (_, y, _) if y == point_a.1 => {
-                self.fill_flat_triangle(point_c, point_a, point_b, color)
-            }
-            _ => {
-                let intersection = calculate_intersection(point_c, point_b, point_a);
-                self.fill_flat_triangle(point_a, point_b, intersection, color);
-                self.fill_flat_triangle(point_c, point_b, intersection, color);
-            }
-        }
+    /// # Example
+    /// ```
+    /// use ferrux_viewport::viewport::CoordSpace;
+    ///
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_coord_space(CoordSpace::UnitTopLeft);
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]); // top left corner
+    /// assert_eq!(viewport.dirty_bounds(), Some((0, 0, 0, 0)));
+    /// ```
+    pub fn set_coord_space(&mut self, coord_space: CoordSpace) {
+        self.mapping.coord_space = coord_space;
     }
 
-    /// Uses BresenhamZip to push the pixels to draw and fill a flat Y triangle (top or bot)
-    fn fill_flat_triangle<'a>(
-        &mut self,
-        peak: Voxel<isize>,
-        side_a: Voxel<isize>,
-        side_b: Voxel<isize>,
-        color: &'a [u8],
-    ) {
-        let bresenham = build_zip!(3D:Y - peak -> side_a, side_b)
-            .expect("Side points of a flat triangle should share the same Y value");
-        for (left, right) in bresenham {
-            self.push_line(left, right, color);
-        }
+    /// Sets if the lines and the filled triangles have to be anti-aliased. When enabled, [`Viewport::draw_line`] and
+    /// [`Viewport::fill_triangle`] work like [`Viewport::draw_line_aa`] and [`Viewport::fill_triangle_aa`], and so do
+    /// the rest of primitives drawn with them, like the outlined triangles or the curves. It's disabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled`, if the following drawings have to be anti-aliased.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_antialiasing(true);
+    /// viewport.draw_line((-0.5, -0.5, 0.0), (0.5, 0.0, 0.0), [255, 255, 255, 255]); // same as draw_line_aa
+    /// ```
+    pub fn set_antialiasing(&mut self, enabled: bool) {
+        self.antialiasing = enabled;
     }
 
-    /// Resets the buffer clearing all its current content
-    pub fn reset_buffer(&mut self) {
-        self.buffer = vec![Pixel::default(); usize::cast(self.width) * usize::cast(self.height)];
+    /// Sets how [`Viewport::fill_triangle`] and [`Viewport::fill_polygon`] draw their shapes, like `glPolygonMode`.
+    /// They can be filled, the default, or reduced to their edges or their vertices, to flip a whole scene between
+    /// solid and wireframe without changing the drawing calls.
+    ///
+    /// # Arguments
+    /// * `mode`, way to draw the filled shapes in the following drawings.
+    ///
+    /// # Example
+    /// ```
+    /// use ferrux_viewport::viewport::PolygonMode;
+    ///
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_polygon_mode(PolygonMode::Line);
+    /// viewport.fill_triangle((0.0, 0.0, 0.0), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.get_pixel((0.0, 0.25, 0.0)), Some(([0, 0, 0, 0], 0))); // the inside is empty
+    /// ```
+    pub fn set_polygon_mode(&mut self, mode: PolygonMode) {
+        self.polygon_mode = mode;
     }
-}
 
-impl<S: PixelSize, R: Resize<S>> Viewport<S, R> {
-    /// Changes the size of the rendered window. Doing it will **reset the buffer**, clearing the current content.
+    /// Sets the winding of the triangles to skip in [`Viewport::draw_triangle`] and [`Viewport::fill_triangle`],
+    /// useful to not draw the faces of a closed mesh looking away. By default no triangle is skipped.
+    /// The winding is checked on the screen, so it's flipped by [`Viewport::set_y_up`] and mirroring transforms.
+    /// Triangles without area are never skipped.
     ///
     /// # Arguments
-    /// * `width`. New width of the window.
-    /// * `height`. New height of the window.
+    /// * `mode`, winding of the triangles to skip in the following drawings.
     ///
-    pub fn resize(&mut self, width: S, height: S) {
-        self.width = width;
-        self.height = height;
-		self.reset_buffer();
-        self.renderer.resize(width, height);
+    /// # Example
+    /// ```
+    /// use ferrux_viewport::viewport::CullMode;
+    ///
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_cull_mode(CullMode::Clockwise);
+    /// viewport.fill_triangle((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.0, 0.5, 0.0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.dirty_bounds(), None);
+    /// ```
+    pub fn set_cull_mode(&mut self, mode: CullMode) {
+        self.cull = mode;
     }
-}
 
-impl<S: PixelSize, R: Render> Viewport<S, R> {
-    /// Renders the content of the buffer in the Window. 
-	/// It doesn't clear the buffer afterwards, to do that call [Viewport::reset_buffer].
-	/// 
-	/// # Example
-    /// ```no_run
-	/// # use std::error::Error;
-	/// # fn main() -> Result<(), Box<dyn Error>> {
-    /// # let event_loop = winit::event_loop::EventLoop::new();
-    /// # let window = winit::window::Window::new(&event_loop).unwrap();
-    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), &[255, 255, 255, 255]);
-    /// viewport.render()?; // renders the triangle in the window
-	/// viewport.reset_buffer(); // clears the buffer to prepare the drawing of a new frame
-	/// # Ok (())
-	/// # }
+    /// Sets the orientation of the `y` axis. By default it goes from north to south, enabling this flag
+    /// it will go from south to north, so `y = -1.0` will be the bottom row and `y = 1.0` the top one.
+    /// The functions working directly with pixel coordinates are not affected.
+    ///
+    /// # Arguments
+    /// * `enabled`, true to use the `y` axis upwards, false to use it downwards.
+    ///
+    /// # Example
     /// ```
-    pub fn render(&mut self) -> Result<(), ViewportError> {
-        self.renderer.render(&self.buffer)
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_y_up(true);
+    /// viewport.draw_point((-1.0, -1.0, 0.0), [255, 255, 255, 255]); // bottom left corner
+    /// ```
+    pub fn set_y_up(&mut self, enabled: bool) {
+        self.mapping.y_up = enabled;
     }
 
-    /// Draws an empty frame without the needing of resetting the buffer.
-	/// This is an optimal way of drawing an empty frame keeping the current drawing buffer without the need
-	/// to save it, resetting buffer, rendering and redrawing it.
-	/// 
-	/// # Example
-    /// ```no_run
-	/// # use std::error::Error;
-	/// # fn main() -> Result<(), Box<dyn Error>> {
-    /// # let event_loop = winit::event_loop::EventLoop::new();
-    /// # let window = winit::window::Window::new(&event_loop).unwrap();
-    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
-    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), &[255, 255, 255, 255]);
-    /// viewport.render()?;         // renders the triangle in the window
-	/// viewport.clear_frame()?;    // renders an empty frame
-	/// viewport.fill_triangle((0.25, 0.75, -0.5), (-0.25, 0.25, 0.0), (0.25, 0.25, 0.0), &[255, 0, 0, 255]);
-	/// viewport.render()?;         // renders both triangles as the previous one was not deleted from the buffer
-	/// # Ok (())
-	/// # }
+    /// Sets the clamping of the coordinates out of the screen. By default the points out of the `[-1.0, 1.0]` range
+    /// are not drawn, enabling this flag they will be moved to the closest edge of the screen instead, so partially
+    /// off-screen shapes still draw their visible portion.
+    ///
+    /// Clamping distorts the geometry at the border, as all the off-screen parts of a shape are flattened against it.
+    /// The functions working directly with pixel coordinates are not affected.
+    ///
+    /// It is a shorthand of [`Viewport::set_bounds_policy`] with [`BoundsPolicy::Clamp`] or [`BoundsPolicy::Discard`].
+    ///
+    /// # Arguments
+    /// * `enabled`, true to clamp the coordinates, false to discard the ones out of the screen.
+    ///
+    /// # Example
     /// ```
-    pub fn clear_frame(&mut self) -> Result<(), ViewportError> {
-        self.renderer.clear()
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_clamp(true);
+    /// viewport.draw_point((2.0, 0.0, 0.0), [255, 255, 255, 255]); // drawn at the right edge
+    /// assert_eq!(viewport.get_pixel((0.999, 0.0, 0.0)), Some(([255, 255, 255, 255], 50)));
+    /// ```
+    pub fn set_clamp(&mut self, enabled: bool) {
+        self.set_bounds_policy(if enabled { BoundsPolicy::Clamp } else { BoundsPolicy::Discard });
     }
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{pixel::Pixel, viewport::ViewportFactory};
-
-    #[test]
-    fn draw_point() {
-        let mut viewport = ViewportFactory::test(640, 480, 1000);
-        let color = &[255, 255, 255, 255];
 
-        viewport.draw_point((-1.0, -1.0, -1.0), color);
-        viewport.draw_point((1.0, 1.0, 1.0), color); // will be ignored
-        viewport.draw_point((0.0, 0.0, 0.0), color);
-        viewport.draw_point((0.0, 0.0, 0.5), color); // will override the previous one
-        viewport.draw_point((-0.25, 0.25, 0.25), color);
-        viewport.draw_point((-0.25, 0.25, -0.25), color); // will not override the previous
-
-        assert_eq!(viewport.buffer[0], Pixel::new(color, 0));
-        assert_eq!(viewport.buffer[153920], Pixel::new(color, 750));
-        assert_eq!(viewport.buffer[192240], Pixel::new(color, 625));
+    /// Sets how to handle the pixels drawn out of the screen. By default they are discarded, but they can also be
+    /// clamped to the closest edge, the same as [`Viewport::set_clamp`], or reported as an error to debug projection
+    /// bugs. With [`BoundsPolicy::Error`] the first pixel out of the screen is kept and the next call to
+    /// [`Viewport::render`] or [`Viewport::render_dirty`] returns it as [`ViewportError::OutOfBounds`] without rendering.
+    ///
+    /// # Arguments
+    /// * `policy`, way to handle the pixels out of the screen in the following drawings.
+    ///
+    /// # Example
+    /// ```
+    /// use ferrux_viewport::{error::ViewportError, viewport::BoundsPolicy};
+    ///
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_bounds_policy(BoundsPolicy::Error);
+    /// viewport.draw_point((2.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// assert!(matches!(viewport.render(), Err(ViewportError::OutOfBounds { x: 960, y: 240 })));
+    /// ```
+    pub fn set_bounds_policy(&mut self, policy: BoundsPolicy) {
+        self.bounds_policy = policy;
+        self.mapping.clamp = policy == BoundsPolicy::Clamp;
     }
 
-    #[test]
-    fn draw_line() {
-        let mut viewport = ViewportFactory::test(24, 24, 10);
-        let color = &[255, 255, 255, 255];
-
-        viewport.draw_line((-0.25, -0.25, 0.0), (0.25, 0.25, 0.0), color);
-
-        for i in 0..7 {
-            assert_eq!(viewport.buffer[225 + i * 25], Pixel { color: *color, depth: 5 });
+    /// Keeps the first pixel drawn out of the screen to report it, if the bounds policy asks for it
+    fn mark_out_of_bounds(&mut self, x: isize, y: isize) {
+        if self.bounds_policy == BoundsPolicy::Error && self.out_of_bounds.is_none() {
+            self.out_of_bounds = Some((x, y));
         }
     }
 
-    #[test]
-    fn draw_triangle() {
-        let mut viewport = ViewportFactory::test(16, 16, 10);
-        let color = &[255, 255, 255, 255];
-
-        viewport.draw_triangle(
-            (0.0, -0.25, 0.0),
-            (-0.25, 0.0, 0.0),
-            (0.25, 0.0, 0.0),
-            color,
-        );
+    /// Sets the preservation of the aspect ratio in the radii of circles and ellipses. By default each radius is
+    /// relative to the size of its axis, so the circles are stretched in non-square windows. Enabling this flag
+    /// both axes use the scale of the shortest one and the circles are visually round.
+    ///
+    /// When enabled, a circle of radius `1.0` will touch the bounds of the shortest axis but not the ones of the longest.
+    ///
+    /// # Arguments
+    /// * `enabled`, true to use the same scale in both axes, false to use the scale of each axis.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(960, 720, 100).unwrap();
+    /// viewport.set_preserve_aspect(true);
+    /// viewport.fill_circle((0.0, 0.0, 0.0), 0.5, [255, 255, 255, 255]); // 180 pixels of radius in both axes
+    /// ```
+    pub fn set_preserve_aspect(&mut self, enabled: bool) {
+        self.mapping.preserve_aspect = enabled;
+    }
 
-        // Check points in each of the lines
-        assert_eq!(viewport.buffer[119], Pixel { color: *color, depth: 5 });
-        assert_eq!(viewport.buffer[135], Pixel { color: *color, depth: 5 });
-        assert_eq!(viewport.buffer[121], Pixel { color: *color, depth: 5 });
+    /// Changes the depth of the viewport, the resolution used to map the `z` coordinate.
+    /// The depths already in the buffer were computed with the previous one and are not updated,
+    /// so this should usually be followed by a call to [`Viewport::reset_buffer`].
+    ///
+    /// # Arguments
+    /// * `depth`, new depth of the viewport.
+    ///
+    /// # Error
+    /// If the depth is zero, in which case the current one is kept.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_depth(1000).unwrap();
+    /// viewport.reset_buffer();
+    /// assert!(viewport.set_depth(0).is_err());
+    /// assert_eq!(viewport.depth(), 1000);
+    /// ```
+    pub fn set_depth(&mut self, depth: S) -> Result<(), ViewportError> {
+        if depth == S::zero() {
+            return Err(ViewportError::InvalidDimensions {
+                width: u32::cast(self.width),
+                height: u32::cast(self.height),
+                depth: 0,
+            });
+        }
+        self.depth = depth;
+        Ok(())
     }
 
-    #[test]
+    /// Adds a transform to apply to the positions given to the drawing functions, on top of the current ones.
+    /// The new transform is applied first, so the shapes can be drawn in local coordinates and placed with
+    /// the previous transforms. Only the positions are transformed, the radii and sizes are kept and the
+    /// axis-aligned shapes like rectangles stay aligned.
+    ///
+    /// # Arguments
+    /// * `transform`, transform to add.
+    ///
+    /// # Example
+    /// ```
+    /// use ferrux_viewport::transform::Transform2D;
+    ///
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.push_transform(Transform2D::translate(0.5, 0.0));
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]); // drawn at (0.5, 0.0)
+    /// viewport.pop_transform();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]); // drawn at (0.0, 0.0)
+    /// ```
+    pub fn push_transform(&mut self, transform: Transform2D) {
+        let current = self.transforms.last().copied().unwrap_or_default();
+        self.transforms.push(transform.then(current));
+    }
+
+    /// Removes the last transform added with [`Viewport::push_transform`]. Does nothing if there are no transforms.
+    pub fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    /// Confines the following drawings to the rectangle between the given corners, both included.
+    /// The pixels out of it are discarded until the clip is removed with [`Viewport::clear_clip_rect`].
+    /// The corners are in screen coordinates, so the transforms are not applied to them, and their `z` is ignored.
+    ///
+    /// # Arguments
+    /// * `min`, `max`. Coordinates of two opposite corners of the rectangle.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_clip_rect((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0));
+    /// viewport.draw_line((-1.0, 0.0, 0.0), (1.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.dirty_bounds(), Some((160, 240, 480, 240)));
+    /// viewport.clear_clip_rect();
+    /// ```
+    pub fn set_clip_rect<C: Coord>(&mut self, min: Position<C>, max: Position<C>) {
+        let (min, max) = (to_f64(min), to_f64(max));
+        let (ax, ay, _) = to_pixel(min, self.sizes(), self.mapping);
+        let (bx, by, _) = to_pixel(max, self.sizes(), self.mapping);
+        let (x0, y0, x1, y1) = (ax.min(bx), ay.min(by), ax.max(bx), ay.max(by));
+        // a rectangle beyond the left or top edge leaves nothing to draw
+        self.clip = Some(if x1 < 0 || y1 < 0 {
+            (1, 1, 0, 0)
+        } else {
+            (x0.max(0) as usize, y0.max(0) as usize, x1 as usize, y1 as usize)
+        });
+    }
+
+    /// Removes the clip set with [`Viewport::set_clip_rect`], allowing to draw in the whole viewport again
+    pub fn clear_clip_rect(&mut self) {
+        self.clip = None;
+    }
+
+    /// Starts the recording of a new stencil, clearing the previous one. Until [`Viewport::end_stencil`] is called,
+    /// the drawings don't change the buffer and instead they mark their pixels in the stencil, no matter their depth
+    /// or color. Then, with [`Viewport::set_stencil_test`] enabled, the drawings will only affect the marked pixels,
+    /// clipping them to an arbitrary shape.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.begin_stencil();
+    /// viewport.fill_circle((0.0, 0.0, 0.0), 0.5, [255, 255, 255]);
+    /// viewport.end_stencil();
+    /// viewport.set_stencil_test(true);
+    /// viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [255, 0, 0]); // only fills the circle
+    /// assert_eq!(viewport.get_pixel((0.9, 0.9, 0.0)), Some(([0, 0, 0, 0], 0)));
+    /// ```
+    pub fn begin_stencil(&mut self) {
+        self.stencil.fill(false);
+        self.stencil_recording = true;
+    }
+
+    /// Ends the recording of the stencil started with [`Viewport::begin_stencil`], the following drawings will
+    /// change the buffer again. The stencil is kept until the next recording.
+    pub fn end_stencil(&mut self) {
+        self.stencil_recording = false;
+    }
+
+    /// Sets if the drawings have to be limited to the pixels marked in the stencil, see [`Viewport::begin_stencil`].
+    /// It's disabled by default, and enabling it without any stencil recorded will stop all the drawings.
+    ///
+    /// # Arguments
+    /// * `enabled`, if the stencil test has to be applied to the following drawings.
+    pub fn set_stencil_test(&mut self, enabled: bool) {
+        self.stencil_test = enabled;
+    }
+
+    /// Sets if the drawings have to pass the depth test. When disabled, every drawing replaces what's in the buffer
+    /// no matter its `z`, so the last one drawn wins like in a painter's algorithm. The depth is still stored for
+    /// when it's enabled again. It's enabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled`, if the depth test has to be applied to the following drawings.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.set_depth_test(false);
+    /// viewport.draw_point((0.0, 0.0, 0.5), [255, 0, 0, 255]);
+    /// viewport.draw_point((0.0, 0.0, -0.5), [0, 0, 255, 255]); // drawn over the red one
+    /// assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)), Some(([0, 0, 255, 255], 25)));
+    /// ```
+    pub fn set_depth_test(&mut self, enabled: bool) {
+        self.depth_test = enabled;
+    }
+
+    /// Applies the current transform to the position
+    fn transform(&self, position: Position) -> Position {
+        match self.transforms.last() {
+            Some(transform) => transform.apply(position),
+            None => position,
+        }
+    }
+
+    /// Converts the normalized position into its pixel in the viewport. The transforms are applied in `f64`,
+    /// so the transformed positions keep the full precision of the [Coord] too.
+    fn to_pixel<C: Coord>(&self, position: Position<C>) -> Voxel<isize> {
+        to_pixel(self.transform(to_f64(position)), self.sizes(), self.mapping)
+    }
+
+    /// Returns the sizes of the viewport in usize to use in the pixels calculation
+    fn sizes(&self) -> (usize, usize, usize) {
+        (
+            usize::cast(self.width),
+            usize::cast(self.height),
+            usize::cast(self.depth),
+        )
+    }
+
+    /// Adds a pixel to the buffer if it passes the clip and the depth test, mixing it with the previous one using
+    /// the blend mode.
+    fn push_pixel(&mut self, (x, y, z): Voxel<usize>, color: [u8; 4]) {
+        let (width, height) = (usize::cast(self.width), usize::cast(self.height));
+        // fully transparent pixels don't hide what's behind, so they don't take the depth either
+        if color[3] == 0 {
+            return;
+        }
+        if x >= width || y >= height {
+            self.mark_out_of_bounds(x as isize, y as isize);
+            return;
+        }
+        if self.is_clipped(x, y) {
+            return;
+        }
+        let i = buffer_index(x, y, width);
+        if self.stencil_recording {
+            if let Some(marked) = self.stencil.get_mut(i) {
+                *marked = true;
+            }
+            return;
+        }
+        if !self.depth_test || z >= self.depth_buffer[i] {
+            self.buffer[i] = self.blend.blend(color, self.buffer[i]);
+            self.depth_buffer[i] = z;
+            self.mark_dirty(i);
+        }
+    }
+
+    /// Checks if the pixel is out of the clip rectangle, if there's one, or out of the stencil, if it's tested
+    fn is_clipped(&self, x: usize, y: usize) -> bool {
+        let out_of_rect = self
+            .clip
+            .is_some_and(|(x0, y0, x1, y1)| x < x0 || x > x1 || y < y0 || y > y1);
+        let out_of_stencil = self.stencil_test
+            && self.stencil.get(buffer_index(x, y, usize::cast(self.width))) != Some(&true);
+        out_of_rect || out_of_stencil
+    }
+
+    /// Marks the whole buffer as dirty
+    fn mark_all_dirty(&mut self) {
+        self.dirty = Some((0, 0, usize::cast(self.width) - 1, usize::cast(self.height) - 1));
+    }
+
+    /// Extends the dirty region to contain the pixel in the given index of the buffer.
+    fn mark_dirty(&mut self, i: usize) {
+        let width = usize::cast(self.width);
+        let (x, y) = (i % width, i / width);
+        self.dirty = Some(
+            self.dirty
+                .map_or((x, y, x, y), |dirty| merge_bounds(dirty, (x, y, x, y))),
+        );
+    }
+
+    /// Adds the pixels between two points to the buffer using the `push_pixel` function.
+    /// The depth is interpolated along the line, see [`line_voxels`].
+    fn push_line(&mut self, start: Voxel<isize>, end: Voxel<isize>, color: [u8; 4]) {
+        for (x, y, z) in line_voxels(start, end) {
+            if x < 0 || y < 0 {
+                self.mark_out_of_bounds(x, y);
+            } else if z >= 0 {
+                self.push_pixel((x as usize, y as usize, z as usize), color);
+            }
+        }
+    }
+
+    /// Adds a pixel given in signed coordinates to the buffer, ignoring it if it falls out of the screen.
+    fn push_signed_pixel(&mut self, (x, y, z): Voxel<isize>, color: [u8; 4]) {
+        let (width, height, _) = self.sizes();
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            self.mark_out_of_bounds(x, y);
+        } else if z >= 0 {
+            self.push_pixel((x as usize, y as usize, z as usize), color);
+        }
+    }
+
+    /// Adds a horizontal span of pixels to the buffer using the `push_line` function.
+    /// The span is cut to the screen bounds and ignored if its row is out of the screen.
+    fn push_span(
+        &mut self,
+        y: isize,
+        (x_start, z_start): (isize, isize),
+        (x_end, z_end): (isize, isize),
+        color: [u8; 4],
+    ) {
+        let (width, height, _) = self.sizes();
+        let (width, height) = (width as isize, height as isize);
+        let ((x_start, z_start), (x_end, z_end)) = if x_start <= x_end {
+            ((x_start, z_start), (x_end, z_end))
+        } else {
+            ((x_end, z_end), (x_start, z_start))
+        };
+        if y < 0 || y >= height || x_start < 0 || x_end >= width {
+            let x = if x_start < 0 { x_start } else { x_end };
+            self.mark_out_of_bounds(x, y);
+        }
+        if y < 0 || y >= height || x_end < 0 || x_start >= width {
+            return;
+        }
+        let z_at = |x: isize| {
+            if x_end == x_start {
+                z_start
+            } else {
+                z_start + (z_end - z_start) * (x - x_start) / (x_end - x_start)
+            }
+        };
+        let (start, end) = (x_start.max(0), x_end.min(width - 1));
+        self.push_line(
+            (start, y, z_at(start).max(0)),
+            (end, y, z_at(end).max(0)),
+            color,
+        );
+    }
+
+    /// Returns the radius in pixels of both axes for the given normalized radius
+    fn pixel_radii(&self, radius: f32) -> (f32, f32) {
+        let (width, height, _) = self.sizes();
+        let (width, height) = if self.mapping.preserve_aspect {
+            (width.min(height), width.min(height))
+        } else {
+            (width, height)
+        };
+        let radius = match self.mapping.coord_space {
+            CoordSpace::CenteredNormalized => radius * 0.5,
+            CoordSpace::UnitTopLeft => radius,
+        };
+        (radius * width as f32, radius * height as f32)
+    }
+
+    /// Returns the offsets from the center of the points of the first octant of a circle of the given normalized radius.
+    /// The offsets are scaled to the size of each axis, so the other octants can be obtained swapping and negating them.
+    fn circle_offsets(&self, radius: f32) -> Vec<((isize, isize), (isize, isize))> {
+        let (rx, ry) = self.pixel_radii(radius);
+        let r = rx.max(ry);
+        if r < 0.5 {
+            return vec![((0, 0), (0, 0))];
+        }
+        let scale = |value: isize, axis: f32| (value as f32 * axis / r).round() as isize;
+        circle_octant(r.round() as isize)
+            .into_iter()
+            .map(|(x, y)| ((scale(x, rx), scale(y, ry)), (scale(y, rx), scale(x, ry))))
+            .collect()
+    }
+
+    /// Commands the drawing of a point in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The coordinates are given in pixels, skipping the conversion from the normalized space. Points out of the screen are ignored.
+    ///
+    /// # Arguments
+    /// * `position`, coordinates of the point in pixels `(x, y, depth)`.
+    /// * `color`, color of the point to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point_px((320, 240, 50), [255, 255, 255, 255]);
+    /// ```
+    pub fn draw_point_px(&mut self, position: (u32, u32, u32), color: impl Color) {
+        let color = color.to_rgba();
+        self.push_signed_pixel(as_signed(px_to_voxel(position)), color);
+    }
+
+    /// Commands the drawing of a point as a filled square of pixels, all of them at the depth of the point.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// The pixels of the square falling out of the screen are ignored.
+    ///
+    /// # Arguments
+    /// * `position`, coordinates of the center of the point in `(f32, f32, f32)` or `(f64, f64, f64)`.
+    /// * `radius`, number of pixels to extend the point in each direction. A radius of `0` draws a single pixel like
+    ///   [`Viewport::draw_point`], a radius of `1` a square of 3x3 pixels, and so on.
+    /// * `color`, color of the point to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_point_sized((0.0, 0.0, 0.0), 2, [255, 255, 255, 255]); // 5x5 white square in the center
+    /// viewport.render()?; // renders the point in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_point_sized<C: Coord>(&mut self, position: Position<C>, radius: u32, color: impl Color) {
+        let position = to_f64(position);
+        let color = color.to_rgba();
+        if radius == 0 {
+            return self.draw_point(position, color);
+        }
+        let (x, y, z) = self.to_pixel(position);
+        let radius = radius as isize;
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                self.push_signed_pixel((x + dx, y + dy, z), color);
+            }
+        }
+    }
+
+    /// Commands the drawing of a line in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The coordinates are given in pixels, skipping the conversion from the normalized space.
+    ///
+    /// # Arguments
+    /// * `start`, coordinates of the starting point of the line in pixels `(x, y, depth)`.
+    /// * `end`, coordinates of the ending point of the line in pixels `(x, y, depth)`.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_line_px((0, 0, 50), (320, 240, 50), [255, 255, 255, 255]);
+    /// ```
+    pub fn draw_line_px(
+        &mut self,
+        start: (u32, u32, u32),
+        end: (u32, u32, u32),
+        color: impl Color,
+    ) {
+        let color = color.to_rgba();
+        let (start, end) = (as_signed(px_to_voxel(start)), as_signed(px_to_voxel(end)));
+        self.push_line(start, end, color);
+    }
+
+    /// Commands the drawing of a triangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The coordinates are given in pixels, skipping the conversion from the normalized space. 
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle in pixels `(x, y, depth)`.
+    /// * `color`, color of the triangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_triangle_px((320, 0, 50), (0, 479, 50), (639, 479, 50), [255, 255, 255, 255]);
+    /// ```
+    pub fn draw_triangle_px(
+        &mut self,
+        point_a: (u32, u32, u32),
+        point_b: (u32, u32, u32),
+        point_c: (u32, u32, u32),
+        color: impl Color,
+    ) {
+        let color = color.to_rgba();
+        self.draw_line_px(point_a, point_b, color);
+        self.draw_line_px(point_b, point_c, color);
+        self.draw_line_px(point_c, point_a, color);
+    }
+
+    /// Returns the color and depth currently stored in the buffer for the pixel of the given position,
+    /// or `None` if the position falls out of the screen.
+    ///
+    /// # Arguments
+    /// * `position`, coordinates of the point to read, as `f32` or `f64`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)), Some(([255, 255, 255, 255], 50)));
+    /// assert_eq!(viewport.get_pixel((1.5, 1.0, 0.0)), None);
+    /// ```
+    pub fn get_pixel<C: Coord>(&self, position: (C, C, C)) -> Option<([u8; 4], usize)> {
+        let (width, height, _) = self.sizes();
+        let (x, y, _) = self.to_pixel(position);
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return None;
+        }
+        let i = buffer_index(x as usize, y as usize, width);
+        Some((self.buffer[i], self.depth_buffer[i]))
+    }
+
+    /// Returns the depth currently stored in the buffer for the pixel of the given position, the one of the
+    /// frontmost drawing, or `None` if the position falls out of the screen. See [`Viewport::get_pixel`].
+    ///
+    /// # Arguments
+    /// * `position`, coordinates of the point to read, as `f32` or `f64`. Its `z` is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.5), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.get_depth((0.0, 0.0, 0.0)), Some(75));
+    /// assert_eq!(viewport.get_depth((1.5, 1.0, 0.0)), None);
+    /// ```
+    pub fn get_depth<C: Coord>(&self, position: (C, C, C)) -> Option<usize> {
+        self.get_pixel(position).map(|(_, depth)| depth)
+    }
+
+    /// Returns the depth stored in the buffer for the drawings at the given `z`, the value read with
+    /// [`Viewport::get_depth`]. It goes from `0` at `z = -1.0` to the depth of the viewport at `z = 1.0`.
+    ///
+    /// # Arguments
+    /// * `z`, normalized `z` coordinate, as `f32` or `f64`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.5), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.get_depth((0.0, 0.0, 0.0)), Some(viewport.depth_of(0.5)));
+    /// ```
+    pub fn depth_of<C: Coord>(&self, z: C) -> usize {
+        to_depth(z.as_f64(), usize::cast(self.depth))
+    }
+
+    /// Converts the coordinates of a pixel of the rendered frame, like the ones of the cursor, into the position
+    /// used by the drawing functions, the inverse of the conversion of those functions. The current size,
+    /// orientation of the `y` axis and [`CoordSpace`] are considered, but not the transforms.
+    ///
+    /// # Arguments
+    /// * `x`, `y`, coordinates in pixels of the rendered frame. The fractional part moves the position within the pixel,
+    ///   with `0.0` at its center.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// let position = viewport.pixel_to_normalized(320.0, 240.0);
+    /// viewport.draw_point(position, [255, 255, 255, 255]);
+    /// assert_eq!(viewport.get_pixel(position), Some(([255, 255, 255, 255], 50)));
+    /// ```
+    pub fn pixel_to_normalized(&self, x: f64, y: f64) -> Position {
+        let scale = self.scale;
+        let size = (usize::cast(self.width) / scale, usize::cast(self.height) / scale);
+        let (x, y) = to_normalized((x, y), size, self.mapping);
+        (x, y, 0.0)
+    }
+
+    /// Commands the drawing of a point in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// If two drawn points fall on the same pixel, the point with the lowest `z` will be ignored.
+    ///
+    /// # Arguments
+    /// * `position`, coordinates of the point in `(f32, f32, f32)` or `(f64, f64, f64)`.
+    /// * `color`, color of the point to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]); // white point in the center of the screen
+    /// viewport.render()?; // renders the point in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_point<C: Coord>(&mut self, position: (C, C, C), color: impl Color) {
+        let color = color.to_rgba();
+        let voxel = self.to_pixel(position);
+        self.push_signed_pixel(voxel, color);
+    }
+
+    /// Commands the drawing of a batch of points sharing the same color. It will be rendered in the next call
+    /// to [`Viewport::render`].
+    ///
+    /// The result is the same as calling [`Viewport::draw_point`] for each position, but the setup is only
+    /// done once, so it's the preferred way to draw large amounts of points like particles.
+    ///
+    /// # Arguments
+    /// * `points`, list of coordinates of the points to draw.
+    /// * `color`, color of the points to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_points(&[(-0.5, 0.0, 0.0), (0.0, 0.0, 0.0), (0.5, 0.0, 0.0)], [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the three points in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_points<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        let sizes = self.sizes();
+        for &position in &points {
+            let voxel = to_pixel(self.transform(position), sizes, self.mapping);
+            self.push_signed_pixel(voxel, color);
+        }
+    }
+
+    /// Commands the drawing of a line in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The line is clipped to the `[-1.0, 1.0]` depth range, so only the part between the far and near planes is drawn.
+    ///
+    /// # Arguments
+    /// * `start`, coordinates of the starting point of the line.
+    /// * `end`, coordinates of the ending point of the line.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_line((-0.5, -0.5, -0.5), (0.25, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the line in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_line<C: Coord>(&mut self, start: Position<C>, end: Position<C>, color: impl Color) {
+        let (start, end) = (to_f64(start), to_f64(end));
+        let color = color.to_rgba();
+        if let Some((start, end)) = clip_z(start, end) {
+            if self.antialiasing {
+                self.draw_line_aa(start, end, color);
+                return;
+            }
+            let start = self.to_pixel(start);
+            let end = self.to_pixel(end);
+            self.push_line(start, end, color);
+        }
+    }
+
+    /// Commands the drawing of a batch of lines sharing the same color. It will be rendered in the next call
+    /// to [`Viewport::render`].
+    ///
+    /// The result is the same as calling [`Viewport::draw_line`] for each segment, but the setup is only
+    /// done once, so it's the preferred way to draw large amounts of lines like wireframes.
+    ///
+    /// # Arguments
+    /// * `segments`, list of lines to draw as pairs of coordinates of the starting and ending points.
+    /// * `color`, color of the lines to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_lines(&[
+    ///     ((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0)),
+    ///     ((0.5, -0.5, 0.0), (0.0, 0.5, 0.0)),
+    ///     ((0.0, 0.5, 0.0), (-0.5, -0.5, 0.0)),
+    /// ], [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the three lines in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_lines<C: Coord>(&mut self, segments: &[(Position<C>, Position<C>)], color: impl Color) {
+        let segments: Vec<(Position, Position)> =
+            segments.iter().map(|&(start, end)| (to_f64(start), to_f64(end))).collect();
+        let color = color.to_rgba();
+        let sizes = self.sizes();
+        for (start, end) in segments
+            .iter()
+            .filter_map(|&(start, end)| clip_z(start, end))
+        {
+            let start = to_pixel(self.transform(start), sizes, self.mapping);
+            let end = to_pixel(self.transform(end), sizes, self.mapping);
+            self.push_line(start, end, color);
+        }
+    }
+
+    /// Commands the drawing of an open path of lines connecting each point with the next one.
+    /// It will be rendered in the next call to [`Viewport::render`]. Nothing is drawn if less than two points are provided.
+    ///
+    /// # Arguments
+    /// * `points`, coordinates of the points of the path, in order.
+    /// * `color`, color of the path to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let path = [(-0.5, 0.0, 0.0), (-0.25, -0.5, 0.0), (0.25, 0.5, 0.0), (0.5, 0.0, 0.0)];
+    /// viewport.draw_polyline(&path, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the path in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_polyline<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        for segment in points.windows(2) {
+            self.draw_line(segment[0], segment[1], color);
+        }
+    }
+
+    /// Commands the drawing of the outline of a polygon, connecting each point with the next one and the last one
+    /// with the first. It will be rendered in the next call to [`Viewport::render`].
+    /// Nothing is drawn if less than two points are provided.
+    ///
+    /// # Arguments
+    /// * `points`, coordinates of the vertices of the polygon, in order.
+    /// * `color`, color of the polygon to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let pentagon = [(0.0, -0.5, 0.0), (0.5, -0.1, 0.0), (0.3, 0.5, 0.0), (-0.3, 0.5, 0.0), (-0.5, -0.1, 0.0)];
+    /// viewport.draw_polygon(&pentagon, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the pentagon in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_polygon<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        self.draw_polyline(&points, color);
+        if let [first, .., last] = *points {
+            if points.len() > 2 {
+                self.draw_line(last, first, color);
+            }
+        }
+    }
+
+    /// Commands the drawing of a grid covering the whole screen, with the given number of cells per axis.
+    /// It will be rendered in the next call to [`Viewport::render`]. The grid is drawn at the nearest depth, over the
+    /// rest of the drawings, so it can be used to check the mapping of the coordinates.
+    ///
+    /// # Arguments
+    /// * `divisions`, number of cells of each axis. Zero cells draws nothing.
+    /// * `color`, color of the grid to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_grid(4, [64, 64, 64, 255]);
+    /// viewport.render()?; // renders a 4x4 grid in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_grid(&mut self, divisions: u32, color: impl Color) {
+        let color = color.to_rgba();
+        if divisions == 0 {
+            return;
+        }
+        let (min, max) = match self.mapping.coord_space {
+            CoordSpace::CenteredNormalized => (-1.0, 1.0),
+            CoordSpace::UnitTopLeft => (0.0, 1.0),
+        };
+        let lines: Vec<_> = (0..=divisions)
+            .map(|i| min + (max - min) * i as f64 / divisions as f64)
+            .flat_map(|c| [((c, min, 1.0), (c, max, 1.0)), ((min, c, 1.0), (max, c, 1.0))])
+            .collect();
+        self.draw_lines(&lines, color);
+    }
+
+    /// Commands the drawing of an arrow from the start to the end point, with its head at the end.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// The head is formed by two lines at 30 degrees of the shaft, computed in the normalized space.
+    /// If both points are the same, only a point is drawn.
+    ///
+    /// # Arguments
+    /// * `start`, coordinates of the tail of the arrow.
+    /// * `end`, coordinates of the tip of the arrow.
+    /// * `head_size`, normalized length of each of the lines of the head.
+    /// * `color`, color of the arrow to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_arrow((-0.5, 0.5, 0.0), (0.5, -0.5, 0.0), 0.1, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the arrow in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_arrow<C: Coord>(&mut self, start: Position<C>, end: Position<C>, head_size: f32, color: impl Color) {
+        let (start, end) = (to_f64(start), to_f64(end));
+        let color = color.to_rgba();
+        let (dx, dy) = (start.0 - end.0, start.1 - end.1);
+        let length = dx.hypot(dy);
+        if length == 0.0 {
+            self.draw_point(end, color);
+            return;
+        }
+        self.draw_line(start, end, color);
+
+        let head_size = head_size as f64;
+        let (dx, dy) = (dx / length * head_size, dy / length * head_size);
+        let (sin, cos) = std::f64::consts::FRAC_PI_6.sin_cos();
+        for sin in [sin, -sin] {
+            let side = (end.0 + dx * cos - dy * sin, end.1 + dx * sin + dy * cos, end.2);
+            self.draw_line(end, side, color);
+        }
+    }
+
+    /// Commands the drawing of an anti-aliased line in the window using the Xiaolin Wu's algorithm.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    ///
+    /// The coverage of each pixel reduces the alpha of the given color. As the viewport mixes the colors with
+    /// the black background, the edges of the line will fade towards black and not towards whatever is behind,
+    /// unless the [`BlendMode::AlphaOver`] is used.
+    ///
+    /// # Arguments
+    /// * `start`, coordinates of the starting point of the line.
+    /// * `end`, coordinates of the ending point of the line.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_line_aa((-0.5, -0.5, -0.5), (0.25, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the line in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_line_aa<C: Coord>(&mut self, start: Position<C>, end: Position<C>, color: impl Color) {
+        let (start, end) = (to_f64(start), to_f64(end));
+        let color = color.to_rgba();
+        let (mut x0, mut y0, z0) = self.to_pixel(start);
+        let (mut x1, mut y1, z1) = self.to_pixel(end);
+
+        // the line is always walked over the major axis from left to right
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        let ((x0, y0, z0), (x1, y1, z1)) = if x0 > x1 {
+            ((x1, y1, z1), (x0, y0, z0))
+        } else {
+            ((x0, y0, z0), (x1, y1, z1))
+        };
+
+        let length = (x1 - x0) as f32;
+        let (gradient, z_gradient) = if length == 0.0 {
+            (0.0, 0.0)
+        } else {
+            ((y1 - y0) as f32 / length, (z1 - z0) as f32 / length)
+        };
+
+        for x in x0..=x1 {
+            let y = y0 as f32 + gradient * (x - x0) as f32;
+            let z = (z0 as f32 + z_gradient * (x - x0) as f32).round() as isize;
+            let (floor, fract) = (y.floor() as isize, y.fract());
+            let (near, far) = if steep {
+                ((floor, x, z), (floor + 1, x, z))
+            } else {
+                ((x, floor, z), (x, floor + 1, z))
+            };
+            self.push_coverage_pixel(near, color, 1.0 - fract);
+            self.push_coverage_pixel(far, color, fract);
+        }
+    }
+
+    /// Adds a partially covered pixel to the buffer, the color is faded reducing its alpha by the coverage.
+    /// Pixels without coverage are ignored.
+    fn push_coverage_pixel(&mut self, voxel: Voxel<isize>, color: [u8; 4], coverage: f32) {
+        if coverage <= 0.0 {
+            return;
+        }
+        let mut faded = color;
+        faded[3] = (faded[3] as f32 * coverage.min(1.0)).round() as u8;
+        self.push_signed_pixel(voxel, faded);
+    }
+
+    /// Commands the drawing of a line of the given thickness in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The thickness is measured perpendicular to the line, so diagonal lines will keep approximately the same width.
+    /// A thickness of one will draw the same line as [`Viewport::draw_line`] and a thickness of zero will draw nothing.
+    ///
+    /// # Arguments
+    /// * `start`, coordinates of the starting point of the line.
+    /// * `end`, coordinates of the ending point of the line.
+    /// * `thickness`, width of the line in pixels.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_thick_line((-0.5, -0.5, -0.5), (0.25, 0.5, 0.0), 5, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the line in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_thick_line<C: Coord>(
+        &mut self,
+        start: Position<C>,
+        end: Position<C>,
+        thickness: u32,
+        color: impl Color,
+    ) {
+        let (start, end) = (to_f64(start), to_f64(end));
+        let color = color.to_rgba();
+        let start = self.to_pixel(start);
+        let end = self.to_pixel(end);
+        match thickness {
+            0 => return,
+            1 => return self.push_line(start, end, color),
+            _ => {}
+        }
+
+        // the run is stamped over the minor axis, so it's stretched to keep the perpendicular width
+        let (dx, dy) = ((end.0 - start.0) as f32, (end.1 - start.1) as f32);
+        let major = dx.abs().max(dy.abs());
+        let run = if major == 0.0 {
+            thickness as isize
+        } else {
+            (thickness as f32 * dx.hypot(dy) / major).round() as isize
+        };
+        let horizontal = dx.abs() >= dy.abs();
+
+        for (x, y, z) in line_voxels(start, end) {
+            for offset in -(run - 1) / 2..=run / 2 {
+                let point = if horizontal {
+                    (x, y + offset, z)
+                } else {
+                    (x + offset, y, z)
+                };
+                self.push_signed_pixel(point, color);
+            }
+        }
+    }
+
+    /// Commands the drawing of a quadratic Bézier curve in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The curve is drawn as a sequence of lines, the number of them scales with the distance in pixels between the control points.
+    ///
+    /// # Arguments
+    /// * `p0`, coordinates of the starting point of the curve.
+    /// * `p1`, coordinates of the control point of the curve.
+    /// * `p2`, coordinates of the ending point of the curve.
+    /// * `color`, color of the curve to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_quadratic_bezier((-0.5, 0.5, 0.0), (0.0, -0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the curve in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_quadratic_bezier<C: Coord>(
+        &mut self,
+        p0: Position<C>,
+        p1: Position<C>,
+        p2: Position<C>,
+        color: impl Color,
+    ) {
+        let (p0, p1, p2) = (to_f64(p0), to_f64(p1), to_f64(p2));
+        let color = color.to_rgba();
+        self.draw_bezier(&[p0, p1, p2], color);
+    }
+
+    /// Commands the drawing of a cubic Bézier curve in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The curve is drawn as a sequence of lines, the number of them scales with the distance in pixels between the control points.
+    ///
+    /// # Arguments
+    /// * `p0`, coordinates of the starting point of the curve.
+    /// * `p1`, `p2`, coordinates of the control points of the curve.
+    /// * `p3`, coordinates of the ending point of the curve.
+    /// * `color`, color of the curve to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_cubic_bezier((-0.5, 0.0, 0.0), (-0.25, -0.5, 0.0), (0.25, 0.5, 0.0), (0.5, 0.0, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the curve in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_cubic_bezier<C: Coord>(
+        &mut self,
+        p0: Position<C>,
+        p1: Position<C>,
+        p2: Position<C>,
+        p3: Position<C>,
+        color: impl Color,
+    ) {
+        let (p0, p1, p2, p3) = (to_f64(p0), to_f64(p1), to_f64(p2), to_f64(p3));
+        let color = color.to_rgba();
+        self.draw_bezier(&[p0, p1, p2, p3], color);
+    }
+
+    /// Commands the drawing of a Catmull-Rom spline in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Unlike the Bézier curves, the spline passes through all the given points. Each span between two points is drawn
+    /// as a sequence of lines, the number of them scales with the distance in pixels between both points.
+    /// With fewer than four points it's drawn as a [`Viewport::draw_polyline`].
+    ///
+    /// # Arguments
+    /// * `points`, coordinates of the points the spline passes through, in order.
+    /// * `color`, color of the spline to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let points = [(-0.75, 0.0, 0.0), (-0.25, 0.5, 0.0), (0.25, -0.5, 0.0), (0.75, 0.0, 0.0)];
+    /// viewport.draw_spline(&points, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the spline in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_spline<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        if points.len() < 4 {
+            self.draw_polyline(&points, color);
+            return;
+        }
+        let last = points.len() - 1;
+        for i in 0..last {
+            let (p0, p1) = (points[i.saturating_sub(1)], points[i]);
+            let (p2, p3) = (points[i + 1], points[(i + 2).min(last)]);
+            let (x1, y1, _) = self.to_pixel(p1);
+            let (x2, y2, _) = self.to_pixel(p2);
+            let length = ((x2 as f32 - x1 as f32).powi(2) + (y2 as f32 - y1 as f32).powi(2)).sqrt();
+            let segments = ((length / BEZIER_SEGMENT_LENGTH).ceil() as usize).max(1);
+
+            let mut previous = p1;
+            for j in 1..=segments {
+                let next = catmull_rom_point(p0, p1, p2, p3, j as f64 / segments as f64);
+                self.draw_line(previous, next, color);
+                previous = next;
+            }
+        }
+    }
+
+    /// Samples the Bézier curve of the given control points and connects the samples with lines
+    fn draw_bezier(&mut self, controls: &[Position], color: [u8; 4]) {
+        let length: f32 = controls
+            .windows(2)
+            .map(|w| {
+                let (x0, y0, _) = self.to_pixel(w[0]);
+                let (x1, y1, _) = self.to_pixel(w[1]);
+                ((x1 as f32 - x0 as f32).powi(2) + (y1 as f32 - y0 as f32).powi(2)).sqrt()
+            })
+            .sum();
+        let segments = ((length / BEZIER_SEGMENT_LENGTH).ceil() as usize).max(1);
+
+        let mut previous = controls[0];
+        for i in 1..=segments {
+            let next = bezier_point(controls, i as f64 / segments as f64);
+            self.draw_line(previous, next, color);
+            previous = next;
+        }
+    }
+
+    /// Commands the drawing of a triangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the triangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_triangle<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c) = (to_f64(point_a), to_f64(point_b), to_f64(point_c));
+        if self.is_culled(point_a, point_b, point_c) {
+            return;
+        }
+        let color = color.to_rgba();
+        self.draw_line(point_a, point_b, color);
+        self.draw_line(point_b, point_c, color);
+        self.draw_line(point_c, point_a, color);
+    }
+
+    /// Checks if the triangle has to be skipped for having the winding on the screen set in the cull mode
+    fn is_culled(&self, point_a: Position, point_b: Position, point_c: Position) -> bool {
+        let (ax, ay, _) = self.to_pixel(point_a);
+        let (bx, by, _) = self.to_pixel(point_b);
+        let (cx, cy, _) = self.to_pixel(point_c);
+        // the y axis of the screen points down, so a positive area means clockwise
+        let area = (bx - ax) * (cy - ay) - (cx - ax) * (by - ay);
+        match self.cull {
+            CullMode::None => false,
+            CullMode::Clockwise => area > 0,
+            CullMode::CounterClockwise => area < 0,
+        }
+    }
+
+    /// Commands the drawing and filling of a triangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// If the three points are collinear the triangle has no area, so the line covering them is drawn instead.
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
+    /// * `color`, color of the line to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the triangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c) = (to_f64(point_a), to_f64(point_b), to_f64(point_c));
+        if self.is_culled(point_a, point_b, point_c) {
+            return;
+        }
+        match self.polygon_mode {
+            PolygonMode::Fill => {}
+            PolygonMode::Line => return self.draw_triangle(point_a, point_b, point_c, color),
+            PolygonMode::Point => return self.draw_points(&[point_a, point_b, point_c], color),
+        }
+        if self.antialiasing {
+            self.fill_triangle_aa(point_a, point_b, point_c, color);
+            return;
+        }
+        let color = color.to_rgba();
+        let point_a = self.to_pixel(point_a);
+        let point_b = self.to_pixel(point_b);
+        let point_c = self.to_pixel(point_c);
+
+        if is_degenerate(point_a, point_b, point_c) {
+            self.push_line(point_a, point_b, color);
+            self.push_line(point_b, point_c, color);
+            self.push_line(point_c, point_a, color);
+            return;
+        }
+
+        let (point_a, point_b, point_c) = sort_vectors(point_a, point_b, point_c);
+        match point_b {
+            (_, y, _) if y == point_c.1 => {
+                self.fill_flat_triangle(point_a, point_b, point_c, color)
+            }
+            (_, y, _) if y == point_a.1 => {
+                self.fill_flat_triangle(point_c, point_a, point_b, color)
+            }
+            _ => {
+                let intersection = calculate_intersection(point_c, point_b, point_a);
+                self.fill_flat_triangle(point_a, point_b, intersection, color);
+                self.fill_flat_triangle(point_c, point_b, intersection, color);
+            }
+        }
+    }
+
+    /// Commands the drawing and filling of a triangle in the window using a scanline fill. It will be rendered in the
+    /// next call to [`Viewport::render`]. Unlike [`Viewport::fill_triangle`], the left and right ends of each row are
+    /// interpolated over the edges, so every triangle is filled the same way, including the flat and degenerate ones.
+    /// The result can differ from [`Viewport::fill_triangle`] by one pixel at the ends of some rows.
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
+    /// * `color`, color of the triangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle_scanline((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the triangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_scanline<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c) = (to_f64(point_a), to_f64(point_b), to_f64(point_c));
+        if self.is_culled(point_a, point_b, point_c) {
+            return;
+        }
+        match self.polygon_mode {
+            PolygonMode::Fill => {}
+            PolygonMode::Line => return self.draw_triangle(point_a, point_b, point_c, color),
+            PolygonMode::Point => return self.draw_points(&[point_a, point_b, point_c], color),
+        }
+        let color = color.to_rgba();
+        let point_a = self.to_pixel(point_a);
+        let point_b = self.to_pixel(point_b);
+        let point_c = self.to_pixel(point_c);
+
+        for (left, right) in scanline_spans(point_a, point_b, point_c) {
+            self.push_line(left, right, color);
+        }
+    }
+
+    /// Commands the drawing and filling of an anti-aliased triangle in the window. It will be rendered in the next call
+    /// to [`Viewport::render`]. The interior is filled like in [`Viewport::fill_triangle`], while the pixels crossed
+    /// by the edges are partially covered.
+    ///
+    /// The coverage of each border pixel reduces the alpha of the given color. As the viewport mixes the colors with
+    /// the black background, the edges will fade towards black and not towards whatever is behind, unless the
+    /// [`BlendMode::AlphaOver`] is used.
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`. Coordinates of the points of the triangle.
+    /// * `color`, color of the triangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle_aa((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the triangle with smooth edges in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_aa<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c) = (to_f64(point_a), to_f64(point_b), to_f64(point_c));
+        let color = color.to_rgba();
+        let (width, height, _) = self.sizes();
+        let a = self.to_pixel(point_a);
+        let b = self.to_pixel(point_b);
+        let c = self.to_pixel(point_c);
+
+        if is_degenerate(a, b, c) {
+            self.push_line(a, b, color);
+            self.push_line(b, c, color);
+            self.push_line(c, a, color);
+            return;
+        }
+
+        // the border pixels can be up to half a pixel out of the triangle
+        let x_start = (a.0.min(b.0).min(c.0) - 1).max(0);
+        let x_end = (a.0.max(b.0).max(c.0) + 1).min(width as isize - 1);
+        let y_start = (a.1.min(b.1).min(c.1) - 1).max(0);
+        let y_end = (a.1.max(b.1).max(c.1) + 1).min(height as isize - 1);
+        for y in y_start..=y_end {
+            for x in x_start..=x_end {
+                let coverage = edge_distance(a, b, c, (x, y)) + 0.5;
+                if coverage > 0.0 {
+                    let z = plane_depth(a, b, c, (x, y));
+                    self.push_coverage_pixel((x, y, z), color, coverage);
+                }
+            }
+        }
+    }
+
+    /// Commands the drawing of a quadrilateral in the window. It will be rendered in the next call to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`, `point_d`. Coordinates of the corners of the quad in order around it.
+    /// * `color`, color of the quad to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_quad((-0.5, -0.5, 0.0), (0.5, -0.25, 0.0), (0.5, 0.5, 0.0), (-0.25, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the quad in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_quad<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        point_d: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c, point_d) = (to_f64(point_a), to_f64(point_b), to_f64(point_c), to_f64(point_d));
+        let color = color.to_rgba();
+        self.draw_line(point_a, point_b, color);
+        self.draw_line(point_b, point_c, color);
+        self.draw_line(point_c, point_d, color);
+        self.draw_line(point_d, point_a, color);
+    }
+
+    /// Commands the drawing and filling of a quadrilateral in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The quad is filled as the triangles `a, b, c` and `a, c, d`, so the points must be given in order around it.
+    /// If the sides of the quad cross each other the same two triangles are filled, which draws a shape
+    /// different from the expected bow tie.
+    ///
+    /// # Arguments
+    /// * `point_a`, `point_b`, `point_c`, `point_d`. Coordinates of the corners of the quad in order around it.
+    /// * `color`, color of the quad to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_quad((-0.5, -0.5, 0.0), (0.5, -0.25, 0.0), (0.5, 0.5, 0.0), (-0.25, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the quad in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_quad<C: Coord>(
+        &mut self,
+        point_a: Position<C>,
+        point_b: Position<C>,
+        point_c: Position<C>,
+        point_d: Position<C>,
+        color: impl Color,
+    ) {
+        let (point_a, point_b, point_c, point_d) = (to_f64(point_a), to_f64(point_b), to_f64(point_c), to_f64(point_d));
+        let color = color.to_rgba();
+        if is_self_intersecting(point_a, point_b, point_c, point_d) {
+            warn!("The quad {point_a:?}, {point_b:?}, {point_c:?}, {point_d:?} crosses itself");
+        }
+        self.fill_triangle(point_a, point_b, point_c, color);
+        self.fill_triangle(point_a, point_c, point_d, color);
+    }
+
+    /// Commands the drawing and filling of a fan of triangles sharing the center point, each one formed with
+    /// a consecutive pair of points of the rim. It will be rendered in the next call to [`Viewport::render`].
+    /// Nothing is drawn if less than two rim points are provided.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the point shared by all the triangles.
+    /// * `rim`, coordinates of the outer points of the fan, in order.
+    /// * `color`, color of the fan to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle_fan(
+    ///     (0.0, 0.0, 0.0),
+    ///     &[(0.5, 0.0, 0.0), (0.0, 0.5, 0.0), (-0.5, 0.0, 0.0), (0.0, -0.5, 0.0), (0.5, 0.0, 0.0)],
+    ///     [255, 255, 255, 255],
+    /// );
+    /// viewport.render()?; // renders a diamond in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_fan<C: Coord>(&mut self, center: Position<C>, rim: &[Position<C>], color: impl Color) {
+        let center = to_f64(center);
+        let rim: Vec<Position> = rim.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        for pair in rim.windows(2) {
+            self.fill_triangle(center, pair[0], pair[1], color);
+        }
+    }
+
+    /// Commands the drawing and filling of a strip of triangles, each one formed with three consecutive points.
+    /// It will be rendered in the next call to [`Viewport::render`]. Nothing is drawn if less than three points are provided.
+    ///
+    /// # Arguments
+    /// * `points`, coordinates of the points of the strip, alternating between both sides.
+    /// * `color`, color of the strip to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle_strip(
+    ///     &[(-0.5, 0.0, 0.0), (-0.5, 0.5, 0.0), (0.0, 0.0, 0.0), (0.0, 0.5, 0.0), (0.5, 0.0, 0.0)],
+    ///     [255, 255, 255, 255],
+    /// );
+    /// viewport.render()?; // renders the strip in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_strip<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        for triple in points.windows(3) {
+            self.fill_triangle(triple[0], triple[1], triple[2], color);
+        }
+    }
+
+    /// Commands the drawing and filling of a triangle with a color per vertex. The colors are interpolated across the
+    /// triangle, so each pixel mixes the colors of the vertices based on its closeness to them.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `a`, `b`, `c`. Coordinates and color of each point of the triangle.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle_gradient(
+    ///     ((0.0, -0.5, 0.0), [255, 0, 0, 255]),
+    ///     ((-0.5, 0.5, 0.0), [0, 255, 0, 255]),
+    ///     ((0.5, 0.5, 0.0), [0, 0, 255, 255]),
+    /// );
+    /// viewport.render()?; // renders the triangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_gradient<C: Coord>(
+        &mut self,
+        (point_a, color_a): (Position<C>, [u8; 4]),
+        (point_b, color_b): (Position<C>, [u8; 4]),
+        (point_c, color_c): (Position<C>, [u8; 4]),
+    ) {
+        let (point_a, point_b, point_c) = (to_f64(point_a), to_f64(point_b), to_f64(point_c));
+        let (width, height, _) = self.sizes();
+        let a = self.to_pixel(point_a);
+        let b = self.to_pixel(point_b);
+        let c = self.to_pixel(point_c);
+
+        let x_start = a.0.min(b.0).min(c.0).max(0);
+        let x_end = a.0.max(b.0).max(c.0).min(width as isize - 1);
+        let y_start = a.1.min(b.1).min(c.1).max(0);
+        let y_end = a.1.max(b.1).max(c.1).min(height as isize - 1);
+        for y in y_start..=y_end {
+            for x in x_start..=x_end {
+                if let Some((wa, wb, wc)) = barycentric(a, b, c, (x, y)) {
+                    let z = wa * a.2 as f32 + wb * b.2 as f32 + wc * c.2 as f32;
+                    let mut color = [0; 4];
+                    for (i, channel) in color.iter_mut().enumerate() {
+                        let mixed = wa * color_a[i] as f32
+                            + wb * color_b[i] as f32
+                            + wc * color_c[i] as f32;
+                        *channel = mixed.round() as u8;
+                    }
+                    self.push_signed_pixel((x, y, z.round() as isize), color);
+                }
+            }
+        }
+    }
+
+    /// Commands the drawing and filling of a triangle mapping a texture over it. Each vertex carries its `(u, v)`
+    /// coordinates in the texture, from `(0.0, 0.0)` at its top left corner to `(1.0, 1.0)` at the bottom right one.
+    /// The triangle covers the same pixels as [`Viewport::fill_triangle`], interpolating the texture coordinates
+    /// across it, and each pixel takes the color of the nearest texel.
+    /// Fully transparent texels are skipped like in [`Viewport::blit`].
+    /// It will be rendered in the next call to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `verts`, coordinates and texture coordinates of each point of the triangle.
+    /// * `texture`, bytes of the texture, four per pixel and row by row. Missing bytes are treated as transparent.
+    /// * `tex_w`, width in pixels of the texture.
+    /// * `tex_h`, height in pixels of the texture.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let checkers = [
+    ///     255, 255, 255, 255,   0, 0, 0, 255,
+    ///     0, 0, 0, 255,   255, 255, 255, 255,
+    /// ];
+    /// viewport.fill_triangle_textured(
+    ///     [((0.0, -0.5, 0.0), (0.5, 0.0)), ((-0.5, 0.5, 0.0), (0.0, 1.0)), ((0.5, 0.5, 0.0), (1.0, 1.0))],
+    ///     &checkers,
+    ///     2,
+    ///     2,
+    /// );
+    /// viewport.render()?; // renders the triangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_triangle_textured<C: Coord>(
+        &mut self,
+        verts: [(Position<C>, (f32, f32)); 3],
+        texture: &[u8],
+        tex_w: usize,
+        tex_h: usize,
+    ) {
+        let verts = verts.map(|(point, uv)| (to_f64(point), uv));
+        if tex_w == 0 || tex_h == 0 {
+            return;
+        }
+        let [(point_a, uv_a), (point_b, uv_b), (point_c, uv_c)] = verts;
+        let a = self.to_pixel(point_a);
+        let b = self.to_pixel(point_b);
+        let c = self.to_pixel(point_c);
+
+        let texel = |value: f32, size: usize| ((value * size as f32) as usize).min(size - 1);
+        for (left, right) in triangle_spans(a, b, c) {
+            for (x, y, z) in line_voxels(left, right) {
+                let (wa, wb, wc) = barycentric_weights(a, b, c, (x, y)).unwrap_or((1.0, 0.0, 0.0));
+                let u = wa * uv_a.0 + wb * uv_b.0 + wc * uv_c.0;
+                let v = wa * uv_a.1 + wb * uv_b.1 + wc * uv_c.1;
+                let offset = (texel(v, tex_h) * tex_w + texel(u, tex_w)) * 4;
+                let mut color = [0; 4];
+                if let Some(bytes) = texture.get(offset..offset + 4) {
+                    color.copy_from_slice(bytes);
+                }
+                if color[3] != 0 {
+                    self.push_signed_pixel((x, y, z), color);
+                }
+            }
+        }
+    }
+
+    /// Commands the copy of an RGBA image into the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The image is pasted pixel by pixel from the given top left corner, skipping the fully transparent pixels
+    /// and the ones falling out of the screen.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the image. Its `z` is ignored in favour of `depth`.
+    /// * `width`, width in pixels of the image.
+    /// * `height`, height in pixels of the image.
+    /// * `rgba`, bytes of the image, four per pixel and row by row. Missing bytes are treated as transparent.
+    /// * `depth`, normalized depth to draw all the pixels of the image.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let sprite = [
+    ///     255, 0, 0, 255,   0, 0, 0, 0,
+    ///     0, 255, 0, 255,   0, 0, 255, 255,
+    /// ];
+    /// viewport.blit((-0.5, -0.5, 0.0), 2, 2, &sprite, 0.0);
+    /// viewport.render()?; // renders the sprite in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn blit<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        width: usize,
+        height: usize,
+        rgba: &[u8],
+        depth: f32,
+    ) {
+        let top_left = to_f64(top_left);
+        let (x, y, z) = self.to_pixel((top_left.0, top_left.1, depth as f64));
+        for (i, color) in rgba.chunks_exact(4).take(width * height).enumerate() {
+            if color[3] == 0 {
+                continue;
+            }
+            let (dx, dy) = ((i % width) as isize, (i / width) as isize);
+            self.push_signed_pixel(
+                (x + dx, y + dy, z),
+                [color[0], color[1], color[2], color[3]],
+            );
+        }
+    }
+
+    /// Commands the drawing of the wireframe of a triangle mesh in the window. It will be rendered in the next call
+    /// to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `vertices`, coordinates of the vertices of the mesh.
+    /// * `indices`, triples of indices in `vertices` forming each triangle of the mesh.
+    /// * `color`, color of the mesh to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Errors
+    /// If any index is out of the bounds of `vertices` a [`ViewportError::IndexOutOfBounds`] is returned and nothing
+    /// is drawn.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let vertices = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+    /// viewport.draw_mesh(&vertices, &[(0, 1, 2), (0, 2, 3)], [255, 255, 255, 255])?;
+    /// viewport.render()?; // renders the mesh in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_mesh<C: Coord>(
+        &mut self,
+        vertices: &[Position<C>],
+        indices: &[(usize, usize, usize)],
+        color: impl Color,
+    ) -> Result<(), ViewportError> {
+        let vertices: Vec<Position> = vertices.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        for (a, b, c) in mesh_triangles(&vertices, indices)? {
+            self.draw_triangle(a, b, c, color);
+        }
+        Ok(())
+    }
+
+    /// Commands the drawing and filling of a triangle mesh in the window. It will be rendered in the next call
+    /// to [`Viewport::render`].
+    ///
+    /// # Arguments
+    /// * `vertices`, coordinates of the vertices of the mesh.
+    /// * `indices`, triples of indices in `vertices` forming each triangle of the mesh.
+    /// * `color`, color of the mesh to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Errors
+    /// If any index is out of the bounds of `vertices` a [`ViewportError::IndexOutOfBounds`] is returned and nothing
+    /// is drawn.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let vertices = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+    /// viewport.fill_mesh(&vertices, &[(0, 1, 2), (0, 2, 3)], [255, 255, 255, 255])?;
+    /// viewport.render()?; // renders the mesh in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_mesh<C: Coord>(
+        &mut self,
+        vertices: &[Position<C>],
+        indices: &[(usize, usize, usize)],
+        color: impl Color,
+    ) -> Result<(), ViewportError> {
+        let vertices: Vec<Position> = vertices.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        for (a, b, c) in mesh_triangles(&vertices, indices)? {
+            self.fill_triangle(a, b, c, color);
+        }
+        Ok(())
+    }
+
+    /// Commands the drawing of a circle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The radius is relative to the size of each axis, so the circle will be stretched in non-square windows
+    /// unless [`Viewport::set_preserve_aspect`] is enabled.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the circle.
+    /// * `radius`, normalized radius of the circle.
+    /// * `color`, color of the circle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_circle((0.0, 0.0, 0.0), 0.5, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the circle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_circle<C: Coord>(&mut self, center: Position<C>, radius: f32, color: impl Color) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let (cx, cy, cz) = self.to_pixel(center);
+        for ((x, y), (sx, sy)) in self.circle_offsets(radius) {
+            for (dx, dy) in [(x, y), (-x, y), (x, -y), (-x, -y)] {
+                self.push_signed_pixel((cx + dx, cy + dy, cz), color);
+            }
+            for (dx, dy) in [(sx, sy), (-sx, sy), (sx, -sy), (-sx, -sy)] {
+                self.push_signed_pixel((cx + dx, cy + dy, cz), color);
+            }
+        }
+    }
+
+    /// Commands the drawing and filling of a circle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The radius is relative to the size of each axis, so the circle will be stretched in non-square windows
+    /// unless [`Viewport::set_preserve_aspect`] is enabled.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the circle.
+    /// * `radius`, normalized radius of the circle.
+    /// * `color`, color of the circle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_circle((0.0, 0.0, 0.0), 0.5, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the circle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_circle<C: Coord>(&mut self, center: Position<C>, radius: f32, color: impl Color) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let (cx, cy, cz) = self.to_pixel(center);
+        for ((x, y), (sx, sy)) in self.circle_offsets(radius) {
+            for (dx, dy) in [(x, y), (x, -y), (sx, sy), (sx, -sy)] {
+                self.push_span(cy + dy, (cx - dx, cz), (cx + dx, cz), color);
+            }
+        }
+    }
+
+    /// Commands the filling of a sector of a circle, like a slice of a pie chart, in the window. It will be rendered
+    /// in the next call to [`Viewport::render`]. The radius works like in [`Viewport::fill_circle`].
+    ///
+    /// The angles are measured in radians from the positive `x` axis towards the positive `y` axis, so they go
+    /// clockwise on screen unless [`Viewport::set_y_up`] is enabled. The sector goes from the start angle to the
+    /// end one in that direction, wrapping around `0` if the end is lower, and a range of a full turn or more fills
+    /// the whole circle.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the circle.
+    /// * `radius`, normalized radius of the circle.
+    /// * `start_angle`, angle in radians where the sector starts.
+    /// * `end_angle`, angle in radians where the sector ends.
+    /// * `color`, color of the sector to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # use std::f32::consts::PI;
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_sector((0.0, 0.0, 0.0), 0.5, 0.0, 0.6 * PI, [255, 0, 0, 255]);
+    /// viewport.fill_sector((0.0, 0.0, 0.0), 0.5, 0.6 * PI, 0.0, [0, 0, 255, 255]);
+    /// viewport.render()?; // renders a pie chart of two slices in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_sector<C: Coord>(
+        &mut self,
+        center: Position<C>,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        color: impl Color,
+    ) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let (cx, cy, cz) = self.to_pixel(center);
+        let (rx, ry) = self.pixel_radii(radius);
+        let start = start_angle.rem_euclid(TAU);
+        let sweep = if end_angle - start_angle >= TAU {
+            TAU
+        } else {
+            (end_angle - start_angle).rem_euclid(TAU)
+        };
+        let y_sign = if self.mapping.y_up { -1.0 } else { 1.0 };
+        let in_sector = |x: isize, y: isize| {
+            if (x, y) == (0, 0) || sweep == TAU {
+                return true;
+            }
+            // the offsets are scaled by the radii to measure the angles over a circle
+            let angle = (y_sign * y as f32 * rx).atan2(x as f32 * ry);
+            (angle - start).rem_euclid(TAU) <= sweep
+        };
+
+        let mut rows = BTreeMap::new();
+        for ((x, y), (sx, sy)) in self.circle_offsets(radius) {
+            for (dx, dy) in [(x, y), (x, -y), (sx, sy), (sx, -sy)] {
+                let half = rows.entry(dy).or_insert(dx);
+                *half = dx.max(*half);
+            }
+        }
+        for (dy, half) in rows {
+            for dx in -half..=half {
+                if in_sector(dx, dy) {
+                    self.push_signed_pixel((cx + dx, cy + dy, cz), color);
+                }
+            }
+        }
+    }
+
+    /// Commands the drawing of an ellipse in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Each radius is relative to the size of its axis, or to the shortest one if [`Viewport::set_preserve_aspect`]
+    /// is enabled. A zero radius will draw a line over the other axis.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the ellipse.
+    /// * `rx`, normalized radius of the ellipse in the `x` axis.
+    /// * `ry`, normalized radius of the ellipse in the `y` axis.
+    /// * `color`, color of the ellipse to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_ellipse((0.0, 0.0, 0.0), 0.5, 0.25, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the ellipse in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_ellipse<C: Coord>(&mut self, center: Position<C>, rx: f32, ry: f32, color: impl Color) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let (cx, cy, cz) = self.to_pixel(center);
+        for (x, y) in self.ellipse_offsets(rx, ry) {
+            for (dx, dy) in [(x, y), (-x, y), (x, -y), (-x, -y)] {
+                self.push_signed_pixel((cx + dx, cy + dy, cz), color);
+            }
+        }
+    }
+
+    /// Commands the drawing and filling of an ellipse in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// Each radius is relative to the size of its axis, or to the shortest one if [`Viewport::set_preserve_aspect`]
+    /// is enabled. A zero radius will draw a line over the other axis.
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the ellipse.
+    /// * `rx`, normalized radius of the ellipse in the `x` axis.
+    /// * `ry`, normalized radius of the ellipse in the `y` axis.
+    /// * `color`, color of the ellipse to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_ellipse((0.0, 0.0, 0.0), 0.5, 0.25, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the ellipse in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_ellipse<C: Coord>(&mut self, center: Position<C>, rx: f32, ry: f32, color: impl Color) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let (cx, cy, cz) = self.to_pixel(center);
+        for (x, y) in self.ellipse_offsets(rx, ry) {
+            self.push_span(cy + y, (cx - x, cz), (cx + x, cz), color);
+            self.push_span(cy - y, (cx - x, cz), (cx + x, cz), color);
+        }
+    }
+
+    /// Returns the offsets from the center of the points of the first quadrant of an ellipse of the given normalized radii
+    fn ellipse_offsets(&self, rx: f32, ry: f32) -> Vec<(isize, isize)> {
+        let (rx, _) = self.pixel_radii(rx.abs());
+        let (_, ry) = self.pixel_radii(ry.abs());
+        ellipse_quadrant(rx.round() as isize, ry.round() as isize)
+    }
+
+    /// Commands the drawing of an axis-aligned rectangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The corners can be provided in any order, the rectangle will be the one delimited by both.
+    /// The depth is interpolated between the rows of both corners.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the rectangle.
+    /// * `bottom_right`, coordinates of the bottom right corner of the rectangle.
+    /// * `color`, color of the rectangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_rectangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the rectangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_rectangle<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        bottom_right: Position<C>,
+        color: impl Color,
+    ) {
+        let (top_left, bottom_right) = (to_f64(top_left), to_f64(bottom_right));
+        let color = color.to_rgba();
+        let ((left, right), (top, top_z), (bottom, bottom_z)) =
+            self.rectangle_bounds(top_left, bottom_right);
+        self.push_line((left, top, top_z), (right, top, top_z), color);
+        self.push_line((right, top, top_z), (right, bottom, bottom_z), color);
+        self.push_line((right, bottom, bottom_z), (left, bottom, bottom_z), color);
+        self.push_line((left, bottom, bottom_z), (left, top, top_z), color);
+    }
+
+    /// Commands the drawing of the twelve edges of an axis-aligned cuboid in the window.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// There's no perspective, so the near and far faces overlap in the screen and the depth buffer keeps the
+    /// nearest edges, the ones with the higher `z`. The corners can be provided in any order.
+    ///
+    /// # Arguments
+    /// * `min`, `max`. Coordinates of two opposite corners of the cuboid.
+    /// * `color`, color of the cuboid to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_box((-0.5, -0.5, -0.5), (0.5, 0.5, 0.5), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the cuboid in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_box<C: Coord>(&mut self, min: Position<C>, max: Position<C>, color: impl Color) {
+        let (min, max) = (to_f64(min), to_f64(max));
+        let color = color.to_rgba();
+        let corner = |i: usize| {
+            (
+                if i & 1 == 0 { min.0 } else { max.0 },
+                if i & 2 == 0 { min.1 } else { max.1 },
+                if i & 4 == 0 { min.2 } else { max.2 },
+            )
+        };
+        // each edge joins two corners differing in a single axis
+        for i in 0..8 {
+            for axis in [1, 2, 4] {
+                if i & axis == 0 {
+                    self.draw_line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    /// Commands the drawing of the wireframe of a sphere in the window, as a grid of parallels and meridians with
+    /// the poles over the `y` axis. It will be rendered in the next call to [`Viewport::render`].
+    /// The lines are drawn in 3D, so the ones at the back are hidden by the front ones where they cross.
+    /// The radius is relative to the size of each axis, like in [`Viewport::draw_circle`].
+    ///
+    /// # Arguments
+    /// * `center`, coordinates of the center of the sphere.
+    /// * `radius`, normalized radius of the sphere.
+    /// * `segments`, number of bands between the poles, at least two. The meridians are placed at the same angle
+    ///   between them, so there are twice as many, and an even number of bands draws the equator.
+    /// * `color`, color of the sphere to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_sphere((0.0, 0.0, 0.0), 0.5, 12, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the sphere in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_sphere<C: Coord>(&mut self, center: Position<C>, radius: f32, segments: u32, color: impl Color) {
+        let center = to_f64(center);
+        let color = color.to_rgba();
+        let segments = segments.max(2) as usize;
+        let (radius, step) = (radius as f64, std::f64::consts::PI / segments as f64);
+        let point = |latitude: usize, longitude: usize| {
+            let (phi, theta) = (latitude as f64 * step - std::f64::consts::FRAC_PI_2, longitude as f64 * step);
+            (
+                center.0 + radius * phi.cos() * theta.cos(),
+                center.1 + radius * phi.sin(),
+                center.2 + radius * phi.cos() * theta.sin(),
+            )
+        };
+        for latitude in 1..segments {
+            let parallel: Vec<_> = (0..=2 * segments).map(|longitude| point(latitude, longitude)).collect();
+            self.draw_polyline(&parallel, color);
+        }
+        for longitude in 0..2 * segments {
+            let meridian: Vec<_> = (0..=segments).map(|latitude| point(latitude, longitude)).collect();
+            self.draw_polyline(&meridian, color);
+        }
+    }
+
+    /// Commands the drawing and filling of an axis-aligned rectangle in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The corners can be provided in any order, the rectangle will be the one delimited by both.
+    /// The depth is interpolated between the rows of both corners.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the rectangle.
+    /// * `bottom_right`, coordinates of the bottom right corner of the rectangle.
+    /// * `color`, color of the rectangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_rectangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the rectangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_rectangle<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        bottom_right: Position<C>,
+        color: impl Color,
+    ) {
+        let (top_left, bottom_right) = (to_f64(top_left), to_f64(bottom_right));
+        let color = color.to_rgba();
+        let ((left, right), (top, top_z), (bottom, bottom_z)) =
+            self.rectangle_bounds(top_left, bottom_right);
+        for y in top..=bottom {
+            let z = row_depth(y, (top, top_z), (bottom, bottom_z));
+            self.push_line((left, y, z), (right, y, z), color);
+        }
+    }
+
+    /// Commands the filling of an axis-aligned rectangle with a linear gradient in the window. It will be rendered
+    /// in the next call to [`Viewport::render`]. The corners can be provided in any order, the rectangle will be the
+    /// one delimited by both. The depth is interpolated between the rows of both corners.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the rectangle.
+    /// * `bottom_right`, coordinates of the bottom right corner of the rectangle.
+    /// * `start`, color of the left column or the top row.
+    /// * `end`, color of the right column or the bottom row.
+    /// * `direction`, if the color changes along the columns or the rows, see [`GradientDir`].
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # use ferrux_viewport::viewport::GradientDir;
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_rectangle_gradient((-1.0, -1.0, -1.0), (1.0, 1.0, -1.0), [0, 0, 128, 255], [255, 128, 0, 255], GradientDir::Vertical);
+    /// viewport.render()?; // renders a sunset sky in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_rectangle_gradient<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        bottom_right: Position<C>,
+        start: [u8; 4],
+        end: [u8; 4],
+        direction: GradientDir,
+    ) {
+        let (top_left, bottom_right) = (to_f64(top_left), to_f64(bottom_right));
+        let ((left, right), (top, top_z), (bottom, bottom_z)) =
+            self.rectangle_bounds(top_left, bottom_right);
+        let progress = |value: isize, first: isize, last: isize| {
+            if last == first {
+                0.0
+            } else {
+                (value - first) as f32 / (last - first) as f32
+            }
+        };
+        for y in top..=bottom {
+            let z = row_depth(y, (top, top_z), (bottom, bottom_z));
+            for x in left..=right {
+                let t = match direction {
+                    GradientDir::Horizontal => progress(x, left, right),
+                    GradientDir::Vertical => progress(y, top, bottom),
+                };
+                self.push_signed_pixel((x, y, z), lerp_color(start, end, t));
+            }
+        }
+    }
+
+    /// Commands the drawing of an axis-aligned rectangle with rounded corners in the window.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// The corners can be provided in any order, the rectangle will be the one delimited by both.
+    /// The depth is interpolated between the rows of both corners.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the rectangle.
+    /// * `bottom_right`, coordinates of the bottom right corner of the rectangle.
+    /// * `radius`, normalized radius of the corners. It's clamped to half the shorter side of the rectangle.
+    /// * `color`, color of the rectangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_rounded_rect((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), 0.1, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the rectangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_rounded_rect<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        bottom_right: Position<C>,
+        radius: f32,
+        color: impl Color,
+    ) {
+        let (top_left, bottom_right) = (to_f64(top_left), to_f64(bottom_right));
+        let color = color.to_rgba();
+        let ((left, right), (top, top_z), (bottom, bottom_z)) =
+            self.rectangle_bounds(top_left, bottom_right);
+        let ((rx, ry), offsets) = self.rounded_corners(top_left, bottom_right, radius);
+        let (inner_left, inner_right) = (left + rx, right - rx);
+        let (inner_top, inner_bottom) = (top + ry, bottom - ry);
+        let depth = |y: isize| row_depth(y, (top, top_z), (bottom, bottom_z));
+
+        self.push_line((inner_left, top, top_z), (inner_right, top, top_z), color);
+        self.push_line((inner_left, bottom, bottom_z), (inner_right, bottom, bottom_z), color);
+        let (inner_top_z, inner_bottom_z) = (depth(inner_top), depth(inner_bottom));
+        self.push_line((left, inner_top, inner_top_z), (left, inner_bottom, inner_bottom_z), color);
+        self.push_line((right, inner_top, inner_top_z), (right, inner_bottom, inner_bottom_z), color);
+        for (x, y) in offsets {
+            for (cx, dx) in [(inner_left, -x), (inner_right, x)] {
+                self.push_signed_pixel((cx + dx, inner_top - y, depth(inner_top - y)), color);
+                self.push_signed_pixel((cx + dx, inner_bottom + y, depth(inner_bottom + y)), color);
+            }
+        }
+    }
+
+    /// Commands the drawing and filling of an axis-aligned rectangle with rounded corners in the window.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// The corners can be provided in any order, the rectangle will be the one delimited by both.
+    /// The depth is interpolated between the rows of both corners.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the rectangle.
+    /// * `bottom_right`, coordinates of the bottom right corner of the rectangle.
+    /// * `radius`, normalized radius of the corners. It's clamped to half the shorter side of the rectangle.
+    /// * `color`, color of the rectangle to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_rounded_rect((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), 0.1, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the rectangle in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_rounded_rect<C: Coord>(
+        &mut self,
+        top_left: Position<C>,
+        bottom_right: Position<C>,
+        radius: f32,
+        color: impl Color,
+    ) {
+        let (top_left, bottom_right) = (to_f64(top_left), to_f64(bottom_right));
+        let color = color.to_rgba();
+        let ((left, right), (top, top_z), (bottom, bottom_z)) =
+            self.rectangle_bounds(top_left, bottom_right);
+        let ((rx, ry), offsets) = self.rounded_corners(top_left, bottom_right, radius);
+        let (inner_left, inner_right) = (left + rx, right - rx);
+        let (inner_top, inner_bottom) = (top + ry, bottom - ry);
+        let depth = |y: isize| row_depth(y, (top, top_z), (bottom, bottom_z));
+
+        for y in inner_top..=inner_bottom {
+            self.push_span(y, (left, depth(y)), (right, depth(y)), color);
+        }
+        for (x, y) in offsets {
+            for y in [inner_top - y, inner_bottom + y] {
+                self.push_span(y, (inner_left - x, depth(y)), (inner_right + x, depth(y)), color);
+            }
+        }
+    }
+
+    /// Returns the pixel radii of the corners of a rounded rectangle, clamped to half its shorter side,
+    /// along with the offsets of the points of a quarter of the corner from its center
+    fn rounded_corners(
+        &self,
+        corner_a: Position,
+        corner_b: Position,
+        radius: f32,
+    ) -> ((isize, isize), Vec<(isize, isize)>) {
+        let ((left, right), (top, _), (bottom, _)) = self.rectangle_bounds(corner_a, corner_b);
+        let shorter_side = (corner_a.0 - corner_b.0).abs().min((corner_a.1 - corner_b.1).abs());
+        let radius = (radius.abs() as f64).min(shorter_side / 2.0) as f32;
+        let offsets: Vec<(isize, isize)> = self
+            .circle_offsets(radius)
+            .into_iter()
+            .flat_map(|(octant, mirrored)| [octant, mirrored])
+            .map(|(x, y)| (x.min((right - left) / 2), y.min((bottom - top) / 2)))
+            .collect();
+        let rx = offsets.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let ry = offsets.iter().map(|&(_, y)| y).max().unwrap_or(0);
+        ((rx, ry), offsets)
+    }
+
+    /// Converts the corners of a rectangle to pixels and returns its horizontal bounds and its
+    /// top and bottom rows with their respective depth, no matter the order of the given corners
+    fn rectangle_bounds(
+        &self,
+        corner_a: Position,
+        corner_b: Position,
+    ) -> ((isize, isize), (isize, isize), (isize, isize)) {
+        let (ax, ay, az) = self.to_pixel(corner_a);
+        let (bx, by, bz) = self.to_pixel(corner_b);
+        let (top, bottom) = if ay <= by {
+            ((ay, az), (by, bz))
+        } else {
+            ((by, bz), (ay, az))
+        };
+        ((ax.min(bx), ax.max(bx)), top, bottom)
+    }
+
+    /// Commands the filling of the region connected to the seed point sharing its color, like a paint bucket.
+    /// It will be rendered in the next call to [`Viewport::render`].
+    /// The new pixels are drawn at the depth of the seed pixel, so the ones of the region with a higher `z` are kept.
+    ///
+    /// # Arguments
+    /// * `seed`, coordinates of the point to start the filling from.
+    /// * `color`, color to fill the region with. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.draw_rectangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.flood_fill((0.0, 0.0, 0.0), [255, 0, 0, 255]);
+    /// viewport.render()?; // renders a white rectangle filled in red
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn flood_fill<C: Coord>(&mut self, seed: Position<C>, color: impl Color) {
+        let seed = to_f64(seed);
+        let color = color.to_rgba();
+        let (width, height, _) = self.sizes();
+        let (x, y, _) = self.to_pixel(seed);
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let target = self.buffer[buffer_index(x, y, width)];
+        let depth = self.depth_buffer[buffer_index(x, y, width)];
+
+        let mut region = vec![false; self.buffer.len()];
+        let fillable = |region: &[bool], x: usize, y: usize| {
+            let i = buffer_index(x, y, width);
+            !region[i] && self.buffer[i] == target
+        };
+        let mut queue = vec![(x, y)];
+        while let Some((x, y)) = queue.pop() {
+            if !fillable(&region, x, y) {
+                continue;
+            }
+            let mut left = x;
+            while left > 0 && fillable(&region, left - 1, y) {
+                left -= 1;
+            }
+            let mut right = x;
+            while right + 1 < width && fillable(&region, right + 1, y) {
+                right += 1;
+            }
+            for x in left..=right {
+                region[buffer_index(x, y, width)] = true;
+            }
+            let rows = [y.checked_sub(1), Some(y + 1).filter(|&y| y < height)];
+            for y in rows.into_iter().flatten() {
+                let mut in_run = false;
+                for x in left..=right {
+                    let matches = fillable(&region, x, y);
+                    if matches && !in_run {
+                        queue.push((x, y));
+                    }
+                    in_run = matches;
+                }
+            }
+        }
+
+        for (i, _) in region.into_iter().enumerate().filter(|&(_, filled)| filled) {
+            self.push_pixel((i % width, i / width, depth), color);
+        }
+    }
+
+    /// Commands the drawing and filling of a polygon in the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The polygon is closed connecting the last point with the first one and filled following the even-odd rule,
+    /// so concave polygons are supported. The depth of the inner pixels is interpolated from the edges.
+    /// Nothing is drawn if less than three points are provided.
+    ///
+    /// # Arguments
+    /// * `points`. Coordinates of the vertices of the polygon, in order.
+    /// * `color`, color of the polygon to draw. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let square = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+    /// viewport.fill_polygon(&square, [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the polygon in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn fill_polygon<C: Coord>(&mut self, points: &[Position<C>], color: impl Color) {
+        let points: Vec<Position> = points.iter().map(|&point| to_f64(point)).collect();
+        let color = color.to_rgba();
+        if points.len() < 3 {
+            return;
+        }
+        match self.polygon_mode {
+            PolygonMode::Fill => {}
+            PolygonMode::Line => return self.draw_polygon(&points, color),
+            PolygonMode::Point => return self.draw_points(&points, color),
+        }
+        let vertices: Vec<Voxel<isize>> = points
+            .iter()
+            .map(|&point| self.to_pixel(point))
+            .collect();
+        let edges = edge_table(&vertices);
+        let top = vertices.iter().map(|v| v.1).min().unwrap();
+        let bottom = vertices.iter().map(|v| v.1).max().unwrap();
+
+        for y in top..bottom {
+            let mut crossings: Vec<(isize, isize)> = edges
+                .iter()
+                .take_while(|(edge_top, _)| edge_top.1 <= y)
+                .filter(|(_, edge_bottom)| y < edge_bottom.1)
+                .map(|&(edge_top, edge_bottom)| edge_crossing(edge_top, edge_bottom, y))
+                .collect();
+            crossings.sort_unstable();
+            for span in crossings.chunks_exact(2) {
+                self.push_span(y, span[0], span[1], color);
+            }
+        }
+
+        // the outline closes the bottom rows and horizontal edges left out by the scanlines
+        for (&start, &end) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            self.push_line(start, end, color);
+        }
+    }
+
+    /// Fills a flat Y triangle (top or bot), in parallel if the `rayon` feature is enabled.
+    /// The stencil is always recorded sequentially, as well as the drawings reporting the pixels out of bounds.
+    fn fill_flat_triangle(
+        &mut self,
+        peak: Voxel<isize>,
+        side_a: Voxel<isize>,
+        side_b: Voxel<isize>,
+        color: [u8; 4],
+    ) {
+        #[cfg(feature = "rayon")]
+        if !self.stencil_recording && self.bounds_policy != BoundsPolicy::Error {
+            return self.par_fill_flat_triangle(peak, side_a, side_b, color);
+        }
+        self.seq_fill_flat_triangle(peak, side_a, side_b, color);
+    }
+
+    /// Uses BresenhamZip to push the pixels to draw and fill a flat Y triangle (top or bot)
+    fn seq_fill_flat_triangle(
+        &mut self,
+        peak: Voxel<isize>,
+        side_a: Voxel<isize>,
+        side_b: Voxel<isize>,
+        color: [u8; 4],
+    ) {
+        let bresenham = build_zip!(3D:Y - peak -> side_a, side_b)
+            .expect("Side points of a flat triangle should share the same Y value");
+        for (left, right) in bresenham {
+            self.push_line(left, right, color);
+        }
+    }
+
+    /// Uses BresenhamZip to collect the pixels of a flat Y triangle (top or bot) grouped by row,
+    /// and then writes the rows concurrently. The pixels of each row keep the order of the sequential fill.
+    #[cfg(feature = "rayon")]
+    fn par_fill_flat_triangle(
+        &mut self,
+        peak: Voxel<isize>,
+        side_a: Voxel<isize>,
+        side_b: Voxel<isize>,
+        color: [u8; 4],
+    ) {
+        use rayon::prelude::*;
+
+        if color[3] == 0 {
+            return;
+        }
+        let width = usize::cast(self.width);
+        let mut rows: Vec<Vec<(usize, usize)>> = vec![Vec::new(); usize::cast(self.height)];
+        let bresenham = build_zip!(3D:Y - peak -> side_a, side_b)
+            .expect("Side points of a flat triangle should share the same Y value");
+        for (left, right) in bresenham {
+            for (x, y, z) in line_voxels(left, right) {
+                if x < 0 || y < 0 || z < 0 {
+                    continue;
+                }
+                let (x, y) = (x as usize, y as usize);
+                if x >= width || self.is_clipped(x, y) {
+                    continue;
+                }
+                let i = buffer_index(x, y, width);
+                if let Some(row) = rows.get_mut(i / width) {
+                    row.push((i % width, z as usize));
+                }
+            }
+        }
+
+        let (blend, depth_test) = (self.blend, self.depth_test);
+        let drawn = self
+            .buffer
+            .par_chunks_mut(width)
+            .zip(self.depth_buffer.par_chunks_mut(width))
+            .zip(rows.par_iter())
+            .enumerate()
+            .filter_map(|(y, ((colors, depths), pixels))| {
+                let mut drawn: Option<(usize, usize)> = None;
+                for &(x, z) in pixels {
+                    if !depth_test || z >= depths[x] {
+                        colors[x] = blend.blend(color, colors[x]);
+                        depths[x] = z;
+                        drawn = Some(drawn.map_or((x, x), |(x0, x1)| (x0.min(x), x1.max(x))));
+                    }
+                }
+                drawn.map(|(x0, x1)| (x0, y, x1, y))
+            })
+            .reduce_with(merge_bounds);
+        if let Some(drawn) = drawn {
+            self.dirty = Some(self.dirty.map_or(drawn, |dirty| merge_bounds(dirty, drawn)));
+        }
+    }
+
+    /// Returns the bounds of the region drawn since the last render as `(min_x, min_y, max_x, max_y)`,
+    /// both corners included, or [`None`] if nothing has been drawn.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// assert_eq!(viewport.dirty_bounds(), None);
+    /// viewport.draw_line_px((10, 20, 0), (30, 5, 0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.dirty_bounds(), Some((10, 5, 30, 20)));
+    /// ```
+    pub fn dirty_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        self.dirty
+            .map(|(x0, y0, x1, y1)| (x0 as u32, y0 as u32, x1 as u32, y1 as u32))
+    }
+
+    /// Returns the tight bounds of the content of the buffer as `(min_x, min_y, max_x, max_y)`, both corners
+    /// included, or [`None`] if it's empty. Any pixel with a color other than the background counts as content.
+    /// Unlike [`Viewport::dirty_bounds`] it scans the whole buffer, so it doesn't depend on the last render.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// assert_eq!(viewport.content_bounds(), None);
+    /// viewport.draw_line_px((10, 20, 0), (30, 5, 0), [255, 255, 255, 255]);
+    /// assert_eq!(viewport.content_bounds(), Some((10, 5, 30, 20)));
+    /// ```
+    pub fn content_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let width = self.width_px() as usize;
+        self.buffer
+            .iter()
+            .enumerate()
+            .filter(|(_, pixel)| **pixel != self.background)
+            .map(|(i, _)| (i % width, i / width))
+            .fold(None, |bounds, (x, y)| {
+                Some(bounds.map_or((x, y, x, y), |bounds| merge_bounds(bounds, (x, y, x, y))))
+            })
+            .map(|(x0, y0, x1, y1)| (x0 as u32, y0 as u32, x1 as u32, y1 as u32))
+    }
+
+    /// Returns a copy of the buffer as its flattened RGBA bytes, row by row from the top left pixel.
+    /// It's the same layout sent to the renderer, with [`Viewport::width_px`] and [`Viewport::height_px`]
+    /// as dimensions, and the one accepted by [`Viewport::blit`].
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point_px((2, 1, 0), [255, 0, 0, 255]);
+    /// let bytes = viewport.buffer_rgba();
+    /// let offset = (viewport.width_px() as usize + 2) * 4;
+    /// assert_eq!(bytes[offset..offset + 4], [255, 0, 0, 255]);
+    /// ```
+    pub fn buffer_rgba(&self) -> Vec<u8> {
+        self.buffer_bytes().to_vec()
+    }
+
+    /// Returns the buffer as its flattened RGBA bytes without copying it, with the same layout
+    /// of [`Viewport::buffer_rgba`]. The colors are stored contiguously, so this view is free.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point_px((2, 1, 0), [255, 0, 0, 255]);
+    /// let offset = (viewport.width_px() as usize + 2) * 4;
+    /// assert_eq!(viewport.buffer_bytes()[offset..offset + 4], [255, 0, 0, 255]);
+    /// ```
+    pub fn buffer_bytes(&self) -> &[u8] {
+        self.buffer.as_flattened()
+    }
+
+    /// Returns a copy of the current content of the buffer, colors and depths, to restore it later with
+    /// [`Viewport::restore`]. Useful to undo drawings or to reuse a common layer in several passes.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [0, 0, 255]);
+    /// let background = viewport.snapshot();
+    /// viewport.draw_point((0.0, 0.0, 0.5), [255, 0, 0]);
+    /// viewport.restore(&background).unwrap(); // removes the point
+    /// assert_eq!(viewport.snapshot(), background);
+    /// ```
+    pub fn snapshot(&self) -> BufferSnapshot {
+        BufferSnapshot {
+            width: self.width_px(),
+            height: self.height_px(),
+            buffer: self.buffer.clone(),
+            depth_buffer: self.depth_buffer.clone(),
+        }
+    }
+
+    /// Replaces the content of the buffer with a snapshot taken with [`Viewport::snapshot`].
+    /// The whole buffer is marked as dirty.
+    ///
+    /// # Arguments
+    /// * `snapshot`, saved content of the buffer to restore.
+    ///
+    /// # Error
+    /// If the snapshot was taken with a different size, in which case the buffer is kept.
+    pub fn restore(&mut self, snapshot: &BufferSnapshot) -> Result<(), ViewportError> {
+        let (width, height) = (self.width_px(), self.height_px());
+        if (snapshot.width, snapshot.height) != (width, height) {
+            return Err(ViewportError::SnapshotMismatch {
+                width: snapshot.width,
+                height: snapshot.height,
+                expected_width: width,
+                expected_height: height,
+            });
+        }
+        self.buffer.copy_from_slice(&snapshot.buffer);
+        self.depth_buffer.copy_from_slice(&snapshot.depth_buffer);
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Merges the buffer of another viewport over this one, like drawing each one of its pixels again. They pass
+    /// through the depth test and are mixed with the current blend mode, so with [`BlendMode::AlphaOver`] the
+    /// translucent pixels are composited over the current ones. The fully transparent pixels are skipped.
+    /// The depths of the other viewport are scaled to the depth of this one.
+    ///
+    /// # Arguments
+    /// * `other`, viewport with the layer to merge, like a UI drawn apart from the scene.
+    ///
+    /// # Error
+    /// If the other viewport has a different size, in which case the buffer is kept.
+    ///
+    /// # Example
+    /// ```
+    /// let mut scene = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// let mut ui = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// scene.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [0, 0, 255]);
+    /// ui.draw_point((0.0, 0.0, 1.0), [255, 0, 0]);
+    /// scene.composite(&ui).unwrap();
+    /// assert_eq!(scene.get_pixel((0.0, 0.0, 0.0)), Some(([255, 0, 0, 255], 100)));
+    /// ```
+    pub fn composite<T: PixelSize, Q>(&mut self, other: &Viewport<T, Q>) -> Result<(), ViewportError> {
+        let (width, height) = (self.width_px(), self.height_px());
+        if (other.width_px(), other.height_px()) != (width, height) {
+            return Err(ViewportError::SizeMismatch {
+                width: other.width_px(),
+                height: other.height_px(),
+                expected_width: width,
+                expected_height: height,
+            });
+        }
+        let (width, depth, other_depth) = (width as usize, usize::cast(self.depth), usize::cast(other.depth));
+        for (i, (&color, &z)) in other.buffer.iter().zip(&other.depth_buffer).enumerate() {
+            self.push_pixel((i % width, i / width, z * depth / other_depth), color);
+        }
+        Ok(())
+    }
+
+    /// Tints the current content of the buffer, moving the color of every pixel different from the background
+    /// towards the given one. The depths are kept, so it can be applied once the frame is drawn, before rendering.
+    ///
+    /// # Arguments
+    /// * `color`, color to tint the buffer with. It can be any [`Color`], like `[255, 0, 0, 255]` for red with 100% opacity.
+    /// * `strength`, amount of tint from `0.0`, no change, to `1.0`, fully replaced by the color. Clamped to that range.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.fill_rectangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255]);
+    /// viewport.tint([255, 0, 0], 0.5); // damage flash
+    /// assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)).unwrap().0, [255, 128, 128, 255]);
+    /// ```
+    pub fn tint(&mut self, color: impl Color, strength: f32) {
+        let tint = color.to_rgba();
+        let strength = strength.clamp(0.0, 1.0);
+        for color in self.buffer.iter_mut().filter(|color| **color != self.background) {
+            for (channel, tint) in color.iter_mut().zip(tint) {
+                *channel = (*channel as f32 + (tint as f32 - *channel as f32) * strength).round() as u8;
+            }
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Inverts the color of every pixel of the buffer different from the background, turning the RGB channels into
+    /// their negative. The alpha is left untouched, so translucent pixels stay translucent, and the background pixels
+    /// are skipped to keep the empty areas of the frame empty.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 0, 0, 255]);
+    /// viewport.invert();
+    /// assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)).unwrap().0, [0, 255, 255, 255]);
+    /// ```
+    pub fn invert(&mut self) {
+        for color in self.buffer.iter_mut().filter(|color| **color != self.background) {
+            for channel in &mut color[..3] {
+                *channel = u8::MAX - *channel;
+            }
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Runs the given function over every pixel of the buffer, with its coordinates and its color to modify.
+    /// Unlike [`Viewport::tint`] and [`Viewport::invert`], the background pixels are visited too, and the whole
+    /// buffer is marked as dirty even if the function doesn't change any color.
+    ///
+    /// # Arguments
+    /// * `f`, function receiving the `(x, y)` coordinates in pixels and the color of each pixel.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [255, 255, 255]);
+    /// viewport.for_each_pixel(|(_, y), color| {
+    ///     if y % 2 == 1 {
+    ///         color[..3].iter_mut().for_each(|channel| *channel /= 2); // scanlines
+    ///     }
+    /// });
+    /// assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)).unwrap().0, [255, 255, 255, 255]);
+    /// ```
+    pub fn for_each_pixel(&mut self, mut f: impl FnMut((usize, usize), &mut [u8; 4])) {
+        let width = usize::cast(self.width);
+        for (i, color) in self.buffer.iter_mut().enumerate() {
+            f((i % width, i / width), color);
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Mirrors the current content of the buffer from left to right, keeping the depth of each pixel with its color.
+    pub fn flip_horizontal(&mut self) {
+        let width = usize::cast(self.width);
+        let rows = self.buffer.chunks_exact_mut(width);
+        for (row, depths) in rows.zip(self.depth_buffer.chunks_exact_mut(width)) {
+            row.reverse();
+            depths.reverse();
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Mirrors the current content of the buffer from top to bottom, keeping the depth of each pixel with its color.
+    pub fn flip_vertical(&mut self) {
+        let (width, height) = (usize::cast(self.width), usize::cast(self.height));
+        for y in 0..height / 2 {
+            let top = buffer_index(0, y, width);
+            let bottom = buffer_index(0, height - 1 - y, width);
+            for x in 0..width {
+                self.buffer.swap(top + x, bottom + x);
+                self.depth_buffer.swap(top + x, bottom + x);
+            }
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Rotates the current content of the buffer by 180 degrees, keeping the depth of each pixel with its color.
+    /// It's the same as flipping it both horizontally and vertically.
+    pub fn rotate_180(&mut self) {
+        self.buffer.reverse();
+        self.depth_buffer.reverse();
+        self.mark_all_dirty();
+    }
+
+    /// Resets the rectangle between the given corners, both included, filling it with the background color and
+    /// clearing its depths. The rest of the buffer is kept, so it can be used to redraw only a part of the frame.
+    /// Like in [`Viewport::set_clip_rect`], the transforms are not applied to the corners and their `z` is ignored.
+    ///
+    /// # Arguments
+    /// * `min`, `max`. Coordinates of two opposite corners of the rectangle.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [255, 255, 255]);
+    /// viewport.clear_region((0.5, -1.0, 0.0), (1.0, -0.5, 0.0)); // clears the HUD
+    /// assert_eq!(viewport.get_pixel((0.75, -0.75, 0.0)), Some(([0, 0, 0, 0], 0)));
+    /// ```
+    pub fn clear_region<C: Coord>(&mut self, min: Position<C>, max: Position<C>) {
+        let (min, max) = (to_f64(min), to_f64(max));
+        let (width, height, _) = self.sizes();
+        let (ax, ay, _) = to_pixel(min, self.sizes(), self.mapping);
+        let (bx, by, _) = to_pixel(max, self.sizes(), self.mapping);
+        let (x0, x1) = (ax.min(bx).max(0), ax.max(bx).min(width as isize - 1));
+        let (y0, y1) = (ay.min(by).max(0), ay.max(by).min(height as isize - 1));
+        if x0 > x1 || y0 > y1 {
+            return;
+        }
+        let (x0, x1, y0, y1) = (x0 as usize, x1 as usize, y0 as usize, y1 as usize);
+        for y in y0..=y1 {
+            let row = buffer_index(x0, y, width)..=buffer_index(x1, y, width);
+            self.buffer[row.clone()].fill(self.background);
+            self.depth_buffer[row].fill(usize::MIN);
+        }
+        let region = (x0, y0, x1, y1);
+        self.dirty = Some(self.dirty.map_or(region, |dirty| merge_bounds(dirty, region)));
+    }
+
+    /// Resets the buffer clearing all its current content and filling it with the background color
+    pub fn reset_buffer(&mut self) {
+        let buffer_size = usize::cast(self.width) * usize::cast(self.height);
+        self.buffer.resize(buffer_size, self.background);
+        self.buffer.fill(self.background);
+        self.depth_buffer.resize(buffer_size, usize::MIN);
+        self.depth_buffer.fill(usize::MIN);
+        self.stencil.resize(buffer_size, false);
+        self.dirty = None;
+        self.cleared = true;
+    }
+}
+
+#[cfg(feature = "image")]
+impl<S: PixelSize, R> Viewport<S, R> {
+    /// Commands the copy of an image into the window. It will be rendered in the next call to [`Viewport::render`].
+    /// The image is converted to RGBA and pasted like in [`Viewport::blit`], skipping the fully transparent pixels
+    /// and the ones falling out of the screen. Requires the `image` feature.
+    ///
+    /// # Arguments
+    /// * `top_left`, coordinates of the top left corner of the image. Its `z` is ignored in favour of `depth`.
+    /// * `image`, image to draw, like the ones loaded with `image::open`.
+    /// * `depth`, normalized depth to draw all the pixels of the image.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let sprite = image::open("sprite.png")?;
+    /// viewport.draw_image((-0.5, -0.5, 0.0), &sprite, 0.0);
+    /// viewport.render()?; // renders the sprite in the window
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn draw_image<C: Coord>(&mut self, top_left: Position<C>, image: &image::DynamicImage, depth: f32) {
+        let top_left = to_f64(top_left);
+        let image = image.to_rgba8();
+        let (width, height) = image.dimensions();
+        self.blit(top_left, width as usize, height as usize, image.as_raw(), depth);
+    }
+
+    /// Returns a copy of the current content of the buffer as an image. Requires the `image` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point_px((2, 1, 0), [255, 0, 0, 255]);
+    /// let image = viewport.to_image();
+    /// assert_eq!(image.dimensions(), (640, 480));
+    /// assert_eq!(image.get_pixel(2, 1).0, [255, 0, 0, 255]);
+    /// ```
+    pub fn to_image(&self) -> image::RgbaImage {
+        image::RgbaImage::from_raw(self.width_px(), self.height_px(), self.buffer_rgba())
+            .expect("The buffer should have the size of the viewport")
+    }
+}
+
+#[cfg(feature = "png")]
+impl<S: PixelSize, R> Viewport<S, R> {
+    /// Saves the current content of the buffer as a PNG image in the given path.
+    /// Requires the `png` feature.
+    ///
+    /// # Arguments
+    /// * `path`, path of the file to write.
+    ///
+    /// # Error
+    /// If the image can't be encoded or written.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.save_png("triangle.png")?;
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn save_png(&self, path: impl AsRef<std::path::Path>) -> Result<(), ViewportError> {
+        image::save_buffer(
+            path,
+            self.buffer_bytes(),
+            self.width_px(),
+            self.height_px(),
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| match e {
+            image::ImageError::IoError(e) => ViewportError::Io(e),
+            e => ViewportError::Io(std::io::Error::other(e)),
+        })
+    }
+}
+
+impl<S: PixelSize, R: Resize<S>> Viewport<S, R> {
+    /// Changes the size of the rendered window. Doing it will **reset the buffer**, clearing the current content.
+    /// Resizes to an empty window, with a width or height of zero, are ignored.
+    ///
+    /// # Arguments
+    /// * `width`. New width of the window.
+    /// * `height`. New height of the window.
+    ///
+    pub fn resize(&mut self, width: S, height: S) {
+        if usize::cast(width) == 0 || usize::cast(height) == 0 {
+            return;
+        }
+        self.width = width * S::cast(self.scale);
+        self.height = height * S::cast(self.scale);
+		self.reset_buffer();
+        self.renderer.resize(width, height);
+    }
+
+    /// Changes the size of the rendered window keeping the current content of the buffer.
+    /// The pixels of the overlapping region stay in the same pixel coordinates, the ones out of the new size are
+    /// discarded and the new area is filled with the background color. Like in [`Viewport::resize`], resizes to an
+    /// empty window are ignored.
+    ///
+    /// # Arguments
+    /// * `width`. New width of the window.
+    /// * `height`. New height of the window.
+    ///
+    pub fn resize_preserving(&mut self, width: S, height: S) {
+        if usize::cast(width) == 0 || usize::cast(height) == 0 {
+            return;
+        }
+        let (old_width, old_height) = (usize::cast(self.width), usize::cast(self.height));
+        let buffer = std::mem::take(&mut self.buffer);
+        let depth_buffer = std::mem::take(&mut self.depth_buffer);
+        self.resize(width, height);
+
+        let (new_width, new_height) = (usize::cast(self.width), usize::cast(self.height));
+        let row_length = old_width.min(new_width);
+        for y in 0..old_height.min(new_height) {
+            let (old, new) = (buffer_index(0, y, old_width), buffer_index(0, y, new_width));
+            self.buffer[new..new + row_length].copy_from_slice(&buffer[old..old + row_length]);
+            self.depth_buffer[new..new + row_length]
+                .copy_from_slice(&depth_buffer[old..old + row_length]);
+        }
+    }
+
+    /// Handles the window events affecting the viewport, resizing it when the window is resized or its scale
+    /// factor changes. Any other event is ignored, as well as the resizes to an empty window, like when minimized.
+    ///
+    /// # Arguments
+    /// * `event`. Event received by the event loop.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use winit::event::Event;
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// event_loop.run(move |event, _, _| {
+    ///     if let Event::WindowEvent { event, .. } = event {
+    ///         viewport.handle_event(&event); // keeps the viewport size in sync with the window
+    ///     }
+    /// });
+    /// ```
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        let size = match event {
+            WindowEvent::Resized(size) => *size,
+            WindowEvent::ScaleFactorChanged { new_inner_size, .. } => **new_inner_size,
+            _ => return,
+        };
+        if size.width > 0 && size.height > 0 {
+            self.resize(S::cast(size.width), S::cast(size.height));
+        }
+    }
+}
+
+impl<S: PixelSize, R: Render> Viewport<S, R> {
+    /// Renders the content of the buffer in the Window. 
+	/// It doesn't clear the buffer afterwards, to do that call [Viewport::reset_buffer].
+	/// 
+	/// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?; // renders the triangle in the window
+	/// viewport.reset_buffer(); // clears the buffer to prepare the drawing of a new frame
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn render(&mut self) -> Result<(), ViewportError> {
+        self.check_bounds()?;
+        self.dirty = None;
+        self.cleared = false;
+        self.rendered_hash = None;
+        if let Some(clock) = &mut self.clock {
+            clock.tick();
+        }
+        if self.is_raw_output() {
+            return self.renderer.render(&self.buffer);
+        }
+        let output = self.output();
+        self.renderer.render(&output)
+    }
+
+    /// Renders the content of the buffer like [`Viewport::render`], but only if it changed since the last render.
+    /// The buffer is considered unchanged when nothing has been drawn nor reset since then, or when the frame drawn
+    /// again after a reset is identical to the last one rendered by this function, which is checked with a hash.
+    /// The changes in the output settings, like [`Viewport::set_fog`], need a [`Viewport::render`] to be shown.
+    ///
+    /// # Error
+    /// If the renderer fails or a pixel was drawn out of the screen with [`BoundsPolicy::Error`].
+    ///
+    /// # Example
+    /// ```
+    /// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// assert!(viewport.render_if_changed().unwrap());
+    /// assert!(!viewport.render_if_changed().unwrap()); // nothing new to show
+    /// viewport.reset_buffer();
+    /// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]);
+    /// assert!(!viewport.render_if_changed().unwrap()); // the same frame again
+    /// ```
+    pub fn render_if_changed(&mut self) -> Result<bool, ViewportError> {
+        if self.dirty.is_none() && !self.cleared {
+            return Ok(false);
+        }
+        let hash = self.buffer_hash();
+        if self.rendered_hash == Some(hash) {
+            self.dirty = None;
+            self.cleared = false;
+            return Ok(false);
+        }
+        self.render()?;
+        self.rendered_hash = Some(hash);
+        Ok(true)
+    }
+
+    /// Returns the hash of the colors of the buffer, to compare frames
+    fn buffer_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.buffer.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Renders only the region of the buffer drawn since the last render, see [`Viewport::dirty_bounds`].
+    /// The rest of the frame keeps the previously rendered content, so after resetting the buffer a full
+    /// [`Viewport::render`] is needed to clear the old drawings.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?;         // renders the whole frame
+    /// viewport.draw_point((0.9, 0.9, 0.0), [255, 0, 0, 255]);
+    /// viewport.render_dirty()?;   // only uploads the new point
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn render_dirty(&mut self) -> Result<(), ViewportError> {
+        self.check_bounds()?;
+        self.rendered_hash = None;
+        let (x0, y0, x1, y1) = match self.dirty.take() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        if let Some(clock) = &mut self.clock {
+            clock.tick();
+        }
+        let scale = self.scale;
+        let width = (usize::cast(self.width) / scale) as u32;
+        let bounds = (
+            (x0 / scale) as u32,
+            (y0 / scale) as u32,
+            (x1 / scale) as u32,
+            (y1 / scale) as u32,
+        );
+        if self.is_raw_output() {
+            return self.renderer.render_region(&self.buffer, width, bounds);
+        }
+        let output = self.output();
+        self.renderer.render_region(&output, width, bounds)
+    }
+
+    /// Renders the content of the buffer with each one of the given renderers, to show it in more than one window
+    /// or to keep a copy in memory. The own renderer of the viewport is not included and neither the dirty region
+    /// nor the frame clock are updated, so it's meant to be called along with [`Viewport::render`].
+    /// Each renderer keeps its own size, resizing them is left to the caller.
+    ///
+    /// # Arguments
+    /// * `renderers`, renderers to send the frame to, like a [`WinitRenderer`] of another window.
+    ///
+    /// # Errors
+    /// If any renderer fails, stopping at the first failure.
+    ///
+    /// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let minimap_window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// let mut minimap = ferrux_viewport::render::WinitRenderer::new(&minimap_window)?;
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?;                     // renders the triangle in the main window
+    /// viewport.render_to(&mut [&mut minimap])?; // and in the minimap
+	/// # Ok (())
+	/// # }
+    /// ```
+    ///
+    /// [`WinitRenderer`]: crate::render::WinitRenderer
+    pub fn render_to(&self, renderers: &mut [&mut dyn Render]) -> Result<(), ViewportError> {
+        let output;
+        let frame = if self.is_raw_output() {
+            &self.buffer
+        } else {
+            output = self.output();
+            &output
+        };
+        for renderer in renderers.iter_mut() {
+            renderer.render(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first pixel drawn out of the screen since the last check as an error, if any
+    fn check_bounds(&mut self) -> Result<(), ViewportError> {
+        match self.out_of_bounds.take() {
+            Some((x, y)) => Err(ViewportError::OutOfBounds { x, y }),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks if the buffer can be sent as it is to the renderer, without any processing of the output
+    fn is_raw_output(&self) -> bool {
+        self.scale == 1 && self.fog.is_none() && self.output_lut.is_none() && !self.premultiplied
+    }
+
+    /// Returns the frame to send to the renderer, applying the fog, downsampling the buffer to the size of the screen,
+    /// applying the output table and premultiplying the alpha if enabled
+    fn output(&self) -> Vec<[u8; 4]> {
+        let width = usize::cast(self.width);
+        let mut output = match (self.fog, self.scale) {
+            (Some(fog), 1) => self.fogged(fog),
+            (Some(fog), scale) => downsample(&self.fogged(fog), width, scale),
+            (None, 1) => self.buffer.clone(),
+            (None, scale) => downsample(&self.buffer, width, scale),
+        };
+        if let Some(lut) = &self.output_lut {
+            for color in output.iter_mut() {
+                *color = map_color(*color, lut);
+            }
+        }
+        if self.premultiplied {
+            for [r, g, b, a] in output.iter_mut() {
+                for channel in [r, g, b] {
+                    *channel = ((*channel as u16 * *a as u16 + 127) / 255) as u8;
+                }
+            }
+        }
+        output
+    }
+
+    /// Returns a copy of the buffer with each pixel mixed with the fog color in proportion to its depth
+    fn fogged(&self, (fog, near, far): ([u8; 4], f32, f32)) -> Vec<[u8; 4]> {
+        let depth = usize::cast(self.depth) as f32;
+        self.buffer
+            .iter()
+            .zip(&self.depth_buffer)
+            .map(|(color, &z)| {
+                let z = z as f32 / depth * 2.0 - 1.0;
+                let factor = if z >= near {
+                    0.0
+                } else if z <= far {
+                    1.0
+                } else {
+                    (near - z) / (near - far)
+                };
+                lerp_color(*color, fog, factor)
+            })
+            .collect()
+    }
+
+    /// Draws an empty frame of the background color without the needing of resetting the buffer.
+	/// The frame is always fully opaque, ignoring the alpha of the background, so the default one clears it to black.
+	/// This is an optimal way of drawing an empty frame keeping the current drawing buffer without the need
+	/// to save it, resetting buffer, rendering and redrawing it.
+	/// 
+	/// # Example
+    /// ```no_run
+	/// # use std::error::Error;
+	/// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let event_loop = winit::event_loop::EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut viewport = ferrux_viewport::viewport::ViewportFactory::winit(&window, 100).unwrap();
+    /// viewport.fill_triangle((0.0, 0.0, -0.5), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), [255, 255, 255, 255]);
+    /// viewport.render()?;         // renders the triangle in the window
+	/// viewport.clear_frame()?;    // renders an empty frame
+	/// viewport.fill_triangle((0.25, 0.75, -0.5), (-0.25, 0.25, 0.0), (0.25, 0.25, 0.0), [255, 0, 0, 255]);
+	/// viewport.render()?;         // renders both triangles as the previous one was not deleted from the buffer
+	/// # Ok (())
+	/// # }
+    /// ```
+    pub fn clear_frame(&mut self) -> Result<(), ViewportError> {
+        let [r, g, b, _] = self.background;
+        self.renderer.clear([r, g, b, u8::MAX])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{blend::BlendMode, error::ViewportError, util::buffer_index, viewport::ViewportFactory};
+
+    use super::{BoundsPolicy, CoordSpace, CullMode, GradientDir, PolygonMode, Viewport};
+    use crate::render::mock::MockRenderer;
+    use crate::transform::Transform2D;
+    use std::f32::consts::FRAC_PI_2;
+    use winit::{dpi::PhysicalSize, event::WindowEvent};
+
+    const BLANK: ([u8; 4], usize) = ([0, 0, 0, 0], 0);
+
+    /// Returns the color and depth stored in the given index of the buffers
+    fn pixel_at<S, R>(viewport: &Viewport<S, R>, i: usize) -> ([u8; 4], usize) {
+        (viewport.buffer[i], viewport.depth_buffer[i])
+    }
+
+    #[test]
+    fn draw_point() {
+        let mut viewport = ViewportFactory::test(640, 480, 1000);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point((-1.0, -1.0, -1.0), color);
+        viewport.draw_point((1.0, 1.0, 1.0), color); // drawn on the far edge
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        viewport.draw_point((0.0, 0.0, 0.5), color); // will override the previous one
+        viewport.draw_point((-0.25, 0.25, 0.25), color);
+        viewport.draw_point((-0.25, 0.25, -0.25), color); // will not override the previous
+
+        assert_eq!(pixel_at(&viewport, 0), (color, 0));
+        assert_eq!(pixel_at(&viewport, 639 + 479 * 640), (color, 1000));
+        assert_eq!(pixel_at(&viewport, 153920), (color, 750));
+        assert_eq!(pixel_at(&viewport, 192240), (color, 625));
+    }
+
+    #[test]
+    fn draw_point_sized() {
+        let mut sized = ViewportFactory::test(16, 16, 10);
+        let mut point = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        sized.draw_point_sized((0.0, 0.0, 0.0), 0, color);
+        point.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(sized.buffer, point.buffer);
+        assert_eq!(sized.depth_buffer, point.depth_buffer);
+
+        sized.reset_buffer();
+        sized.draw_point_sized((0.0, 0.0, 0.0), 1, color);
+        for y in 7..=9 {
+            for x in 7..=9 {
+                assert_eq!(pixel_at(&sized, y * 16 + x), (color, 5));
+            }
+        }
+        assert_eq!(sized.buffer.iter().filter(|&&pixel| pixel == color).count(), 9);
+
+        sized.reset_buffer();
+        sized.draw_point_sized((-1.0, -1.0, 0.0), 1, color);
+        assert_eq!(sized.buffer.iter().filter(|&&pixel| pixel == color).count(), 4);
+    }
+
+    #[test]
+    fn depth_test() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let near = [255, 0, 0, 255];
+        let far = [0, 0, 255, 255];
+
+        viewport.draw_point((0.0, 0.0, 0.5), near);
+        viewport.draw_point((0.0, 0.0, -0.5), far); // will be rejected
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (near, 7));
+
+        viewport.draw_point((0.0, 0.0, 1.0), far);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (far, 10));
+    }
+
+    #[test]
+    fn dirty_bounds() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        assert_eq!(viewport.dirty_bounds(), None);
+
+        viewport.draw_point((0.9, 0.9, 0.0), color);
+        assert_eq!(viewport.dirty_bounds(), Some((15, 15, 15, 15)));
+
+        viewport.draw_point((-1.0, 0.0, 0.0), color);
+        assert_eq!(viewport.dirty_bounds(), Some((0, 8, 15, 15)));
+
+        viewport.reset_buffer();
+        assert_eq!(viewport.dirty_bounds(), None);
+    }
+
+    #[test]
+    fn render_dirty() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.render_dirty().unwrap();
+        assert_eq!(viewport.renderer.render_calls, 0);
+
+        viewport.draw_point((-1.0, -1.0, 0.0), color);
+        viewport.render_dirty().unwrap();
+        assert_eq!(viewport.renderer.render_calls, 1);
+        assert_eq!(viewport.renderer.last_region, Some((0, 0, 0, 0)));
+        assert_eq!(viewport.renderer.last_frame[0], color);
+        assert_eq!(viewport.dirty_bounds(), None);
+
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        viewport.render().unwrap();
+        assert_eq!(viewport.dirty_bounds(), None);
+    }
+
+    #[test]
+    fn render_dirty_headless() {
+        let mut viewport = ViewportFactory::headless(16, 16, 10).unwrap();
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point_px((3, 2, 0), color);
+        viewport.render_dirty().unwrap();
+
+        let frame = viewport.renderer().frame();
+        let i = buffer_index(3, 2, 16) * 4;
+        assert_eq!(frame[i..i + 4], color);
+        assert_eq!(frame.iter().filter(|&&byte| byte != 0).count(), 4);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_fill_triangle() {
+        let mut parallel = ViewportFactory::test(64, 48, 100);
+        let mut sequential = ViewportFactory::test(64, 48, 100);
+        let color = [255, 255, 255, 255];
+        let peak = (10, 2, 90);
+        let (side_a, side_b) = ((0, 40, 10), (63, 40, 50));
+
+        parallel.draw_line((-1.0, 0.0, 0.0), (1.0, 0.0, 0.0), [255, 0, 0, 255]);
+        sequential.draw_line((-1.0, 0.0, 0.0), (1.0, 0.0, 0.0), [255, 0, 0, 255]);
+        parallel.par_fill_flat_triangle(peak, side_a, side_b, color);
+        sequential.seq_fill_flat_triangle(peak, side_a, side_b, color);
+
+        assert_eq!(parallel.buffer, sequential.buffer);
+        assert_eq!(parallel.depth_buffer, sequential.depth_buffer);
+        assert_eq!(parallel.dirty_bounds(), sequential.dirty_bounds());
+    }
+
+    #[test]
+    fn draw_px() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point_px((5, 5, 3), color);
+        viewport.draw_point_px((16, 5, 3), color); // will be ignored
+        assert_eq!(pixel_at(&viewport, buffer_index(5, 5, 16)), (color, 3));
+        assert_eq!(pixel_at(&viewport, buffer_index(0, 6, 16)), BLANK);
+
+        viewport.reset_buffer();
+        viewport.draw_line_px((0, 0, 2), (3, 3, 2), color);
+        for i in 0..4 {
+            assert_eq!(pixel_at(&viewport, buffer_index(i, i, 16)), (color, 2));
+        }
+
+        viewport.reset_buffer();
+        viewport.draw_triangle_px((8, 4, 5), (4, 8, 5), (12, 8, 5), color);
+        let mut normalized = ViewportFactory::test(16, 16, 10);
+        normalized.draw_triangle((0.0, -0.5, 0.0), (-0.5, 0.0, 0.0), (0.5, 0.0, 0.0), color);
+        assert_eq!(viewport.buffer, normalized.buffer);
+        assert_eq!(viewport.depth_buffer, normalized.depth_buffer);
+    }
+
+    #[test]
+    fn get_pixel() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point((-1.0, -1.0, -1.0), color);
+        viewport.draw_point((0.5, -0.5, 0.5), color);
+
+        assert_eq!(viewport.get_pixel((-1.0, -1.0, 0.0)), Some((color, 0)));
+        assert_eq!(viewport.get_pixel((0.5, -0.5, 0.0)), Some((color, 7)));
+        assert_eq!(viewport.get_pixel((0.0, 0.0, 0.0)), Some(([0, 0, 0, 0], 0)));
+        assert_eq!(viewport.get_pixel((1.0, 0.0, 0.0)), Some(([0, 0, 0, 0], 0)));
+        assert_eq!(viewport.get_pixel((0.0, 1.0, 0.0)), Some(([0, 0, 0, 0], 0)));
+        assert_eq!(viewport.get_pixel((1.01, 0.0, 0.0)), None);
+        assert_eq!(viewport.get_pixel((2.0, 2.0, 0.0)), None);
+        assert_eq!(viewport.get_pixel((-1.5, 0.0, 0.0)), None);
+        assert_eq!(viewport.get_pixel((0.0, -1.5, 0.0)), None);
+    }
+
+    #[test]
+    fn get_depth() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        assert_eq!(viewport.get_depth((0.0, 0.0, 0.0)), Some(0));
+
+        viewport.draw_point((0.0, 0.0, 0.6), [255, 0, 0, 255]);
+        viewport.draw_point((0.0, 0.0, -0.6), [0, 255, 0, 255]);
+        assert_eq!(viewport.get_depth((0.0, 0.0, 0.0)), Some(8));
+        assert_eq!(viewport.get_depth((0.0, 0.0, 1.0)), Some(8));
+        assert_eq!(viewport.get_depth((1.5, 0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn draw_point_at_far_edge() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point((1.0, 1.0, 0.0), color);
+        viewport.draw_point((1.0, -1.0, 0.0), color);
+        viewport.draw_point((1.01, 0.0, 0.0), color); // will be ignored
+
+        assert_eq!(pixel_at(&viewport, 255), (color, 5));
+        assert_eq!(pixel_at(&viewport, 15), (color, 5));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 2);
+    }
+
+    #[test]
+    fn draw_rgb_point() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.draw_point((-1.0, -1.0, -1.0), [255, 0, 0]);
+        viewport.draw_point((0.0, 0.0, 0.0), [0, 255, 0, 128]);
+
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 255], 0));
+        assert_eq!(pixel_at(&viewport, 136), ([0, 255, 0, 128], 5));
+    }
+
+    #[test]
+    fn draw_grayscale_point() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.draw_point((-1.0, -1.0, -1.0), [200]);
+
+        assert_eq!(pixel_at(&viewport, 0), ([200, 200, 200, 255], 0));
+    }
+
+    #[test]
+    fn alpha_blending() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.draw_point((-1.0, -1.0, 0.0), [0, 0, 255, 255]);
+        viewport.draw_point((-1.0, -1.0, 0.0), [255, 0, 0, 128]);
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 128], 5));
+
+        viewport.set_blend(BlendMode::AlphaOver);
+        viewport.draw_point((0.0, 0.0, 0.0), [0, 0, 255, 255]);
+        viewport.draw_point((0.0, 0.0, 0.5), [255, 0, 0, 128]);
+        viewport.draw_point((0.0, 0.0, -0.5), [0, 255, 0, 128]); // will be ignored
+        assert_eq!(pixel_at(&viewport, 136), ([128, 0, 127, 255], 7));
+    }
+
+    #[test]
+    fn additive_and_multiply_blending() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.set_blend(BlendMode::Additive);
+        viewport.draw_point((-1.0, -1.0, 0.0), [128, 0, 0, 255]);
+        viewport.draw_point((-1.0, -1.0, 0.0), [128, 0, 0, 255]);
+        viewport.draw_point((-1.0, -1.0, -0.5), [0, 255, 0, 255]); // will be ignored
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 255], 5));
+
+        viewport.set_blend(BlendMode::Replace);
+        viewport.draw_point((0.0, 0.0, 0.0), [10, 20, 30, 255]);
+        viewport.set_blend(BlendMode::Multiply);
+        viewport.draw_point((0.0, 0.0, 0.5), [255, 255, 255, 255]);
+        assert_eq!(pixel_at(&viewport, 136), ([10, 20, 30, 255], 7));
+    }
+
+    #[test]
+    fn draw_f64_point() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point((0.25f64, -0.5f64, 0.5f64), color);
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 10), (color, 7));
+        assert_eq!(viewport.get_pixel((0.25f64, -0.5, 0.5)), viewport.get_pixel((0.25f32, -0.5, 0.5)));
+
+        viewport.push_transform(Transform2D::translate(-0.25, 0.0));
+        viewport.draw_point((0.25f64, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+    }
+
+    #[test]
+    fn draw_f64_shapes() {
+        let mut single = ViewportFactory::test(16, 16, 10);
+        let mut double = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        single.push_transform(Transform2D::rotate(0.3));
+        double.push_transform(Transform2D::rotate(0.3));
+        single.draw_line((-0.75f32, -0.5, -0.5), (0.5, 0.625, 0.5), color);
+        double.draw_line((-0.75f64, -0.5, -0.5), (0.5, 0.625, 0.5), color);
+        single.fill_triangle((-0.5f32, 0.5, 0.0), (0.25, -0.75, 0.25), (0.75, 0.5, -0.25), color);
+        double.fill_triangle((-0.5f64, 0.5, 0.0), (0.25, -0.75, 0.25), (0.75, 0.5, -0.25), color);
+        assert_eq!(single.buffer, double.buffer);
+        assert_eq!(single.depth_buffer, double.depth_buffer);
+
+        // right before the edge between the columns 8 and 9 once translated, which in f32 is the edge itself
+        let x = -0.125 - 1e-10;
+        assert_eq!(x as f32, -0.125);
+        double.reset_buffer();
+        double.pop_transform();
+        double.push_transform(Transform2D::translate(0.25, 0.0));
+        double.draw_line((x, -1.0, 0.0), (x, -0.5, 0.0), color);
+        double.fill_triangle((x, 0.0, 0.0), (x - 0.25, 1.0, 0.0), (x, 1.0, 0.0), color);
+        for y in [0, 4, 8, 15] {
+            assert_eq!(pixel_at(&double, buffer_index(8, y, 16)), (color, 5));
+            assert_eq!(pixel_at(&double, buffer_index(9, y, 16)), BLANK);
+        }
+    }
+
+    #[test]
+    fn draw_packed_point() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.draw_point((-1.0, -1.0, -1.0), 0xFF000080);
+        viewport.draw_point((0.0, 0.0, 0.0), (0, 255, 0, 255));
+
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 128], 0));
+        assert_eq!(pixel_at(&viewport, 136), ([0, 255, 0, 255], 5));
+    }
+
+    #[cfg(feature = "rgb")]
+    #[test]
+    fn draw_rgb_crate_point() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.draw_point((-1.0, -1.0, -1.0), rgb::RGBA8::new(255, 0, 0, 128));
+        viewport.draw_point((0.0, 0.0, 0.0), rgb::RGB8::new(0, 255, 0));
+
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 128], 0));
+        assert_eq!(pixel_at(&viewport, 136), ([0, 255, 0, 255], 5));
+    }
+
+    #[test]
+    fn coord_space() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.set_coord_space(CoordSpace::UnitTopLeft);
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        viewport.draw_point((0.5, 0.75, 1.0), color);
+        assert_eq!(pixel_at(&viewport, 0), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 8), (color, 10));
+
+        viewport.reset_buffer();
+        viewport.draw_circle((0.5, 0.5, 0.0), 0.25, color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 12), (color, 5));
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 13), BLANK);
+
+        viewport.set_coord_space(CoordSpace::CenteredNormalized);
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+    }
+
+    #[test]
+    fn clamp() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.set_bounds_policy(BoundsPolicy::Error);
+        viewport.set_clamp(false);
+        assert_eq!(viewport.bounds_policy, BoundsPolicy::Discard);
+
+        viewport.draw_line((-2.0, 0.0, 0.0), (-1.5, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16), BLANK);
+
+        viewport.set_clamp(true);
+        assert_eq!(viewport.bounds_policy, BoundsPolicy::Clamp);
+        viewport.draw_line((-2.0, 0.0, 0.0), (0.0, 0.0, 0.0), color);
+        for x in 0..=8 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 9), BLANK);
+
+        viewport.draw_point((1.5, 0.5, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 15), (color, 5));
+        assert_eq!(pixel_at(&viewport, 13 * 16), BLANK);
+    }
+
+    #[test]
+    fn push_line_any_direction() {
+        let color = [255, 255, 255, 255];
+        let lines = [
+            ((0, 0, 0), (4, 0, 3)),
+            ((1, 2, 9), (14, 7, 0)),
+            ((3, 15, 1), (12, 0, 10)),
+            ((0, 0, 10), (15, 15, 3)),
+            ((2, 1, 7), (7, 13, 2)),
+        ];
+        for (a, b) in lines {
+            let mut forward = ViewportFactory::test(16, 16, 10);
+            let mut backward = ViewportFactory::test(16, 16, 10);
+            forward.push_line(a, b, color);
+            backward.push_line(b, a, color);
+            assert_eq!(forward.buffer, backward.buffer);
+            assert_eq!(forward.depth_buffer, backward.depth_buffer);
+        }
+    }
+
+    #[test]
+    fn push_line_negative() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.push_line((-4, 8, 5), (4, 8, 5), color);
+        viewport.push_line((-3, -3, 5), (2, 2, 5), color);
+        viewport.push_line((3, 3, -2), (3, 3, 2), color);
+
+        for x in 0..=4 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), (color, 5));
+        }
+        for i in 0..=2 {
+            assert_eq!(pixel_at(&viewport, i * 16 + i), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 3 * 16 + 3), (color, 2));
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 15), BLANK);
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 9);
+    }
+
+    #[test]
+    fn fill_triangle_gradient() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.fill_triangle_gradient(
+            ((-1.0, -1.0, 0.0), [255, 0, 0, 255]),
+            ((0.875, -1.0, 0.0), [0, 255, 0, 255]),
+            ((-1.0, 0.875, 0.0), [0, 0, 255, 255]),
+        );
+
+        assert_eq!(pixel_at(&viewport, 0), ([255, 0, 0, 255], 5));
+        assert_eq!(pixel_at(&viewport, 15), ([0, 255, 0, 255], 5));
+        assert_eq!(pixel_at(&viewport, 15 * 16), ([0, 0, 255, 255], 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 5), ([85, 85, 85, 255], 5));
+        assert_eq!(pixel_at(&viewport, 15 * 16 + 15), BLANK);
+
+        viewport.fill_triangle_gradient(
+            ((-1.0, -1.0, -0.5), [255, 255, 255, 255]),
+            ((0.875, -1.0, -0.5), [255, 255, 255, 255]),
+            ((-1.0, 0.875, -0.5), [255, 255, 255, 255]),
+        ); // will be hidden by the previous triangle
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 5), ([85, 85, 85, 255], 5));
+    }
+
+    #[test]
+    fn blit() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let image = [
+            255, 0, 0, 255, 0, 255, 0, 0, //
+            0, 255, 0, 255, 0, 0, 255, 128,
+        ];
+
+        viewport.draw_point((0.0, -1.0, 0.0), color);
+        viewport.blit((-0.125, -1.0, 0.0), 2, 2, &image, 0.2);
+        assert_eq!(pixel_at(&viewport, 7), ([255, 0, 0, 255], 6));
+        assert_eq!(pixel_at(&viewport, 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 16 + 7), ([0, 255, 0, 255], 6));
+        assert_eq!(pixel_at(&viewport, 16 + 8), ([0, 0, 255, 128], 6));
+
+        viewport.reset_buffer();
+        viewport.blit((0.875, 0.875, 0.0), 2, 2, &image, 0.2);
+        assert_eq!(pixel_at(&viewport, 15 * 16 + 15), ([255, 0, 0, 255], 6));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel != BLANK.0).count(), 1);
+    }
+
+    #[test]
+    fn fill_degenerate_triangle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_triangle((-0.5, 0.0, 0.0), (0.0, 0.0, 0.0), (0.5, 0.0, 0.0), color);
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), (color, 5));
+        }
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 9);
+
+        viewport.reset_buffer();
+        viewport.fill_triangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (0.0, 0.0, 0.0), color);
+        for i in 4..=12 {
+            assert_eq!(pixel_at(&viewport, i * 16 + i), (color, 5));
+        }
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 9);
+
+        viewport.reset_buffer();
+        viewport.fill_triangle((-0.5, -0.5, 0.0), (-0.5, -0.5, 0.0), (-0.5, 0.25, 0.0), color);
+        for y in 4..=10 {
+            assert_eq!(pixel_at(&viewport, y * 16 + 4), (color, 5));
+        }
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 7);
+
+        viewport.reset_buffer();
+        viewport.fill_triangle((0.0, 0.0, 0.0), (0.0, 0.0, 0.0), (0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+    }
+
+    #[test]
+    fn fill_quad() {
+        let mut quad = ViewportFactory::test(16, 16, 10);
+        let mut rectangle = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        quad.fill_quad((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.25, 0.0), (-0.5, 0.25, 0.0), color);
+        rectangle.fill_rectangle((-0.5, -0.5, 0.0), (0.5, 0.25, 0.0), color);
+
+        assert_eq!(quad.buffer, rectangle.buffer);
+        assert_eq!(quad.depth_buffer, rectangle.depth_buffer);
+    }
+
+    #[test]
+    fn fill_triangle_fan() {
+        let mut fan = ViewportFactory::test(16, 16, 10);
+        let mut rectangle = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        fan.fill_triangle_fan(
+            (-0.5, -0.5, 0.0),
+            &[(0.5, -0.5, 0.0), (0.5, 0.25, 0.0), (-0.5, 0.25, 0.0)],
+            color,
+        );
+        rectangle.fill_rectangle((-0.5, -0.5, 0.0), (0.5, 0.25, 0.0), color);
+
+        assert_eq!(fan.buffer, rectangle.buffer);
+        assert_eq!(fan.depth_buffer, rectangle.depth_buffer);
+    }
+
+    #[test]
+    fn fill_triangle_strip() {
+        let mut strip = ViewportFactory::test(16, 16, 10);
+        let mut single = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let points = [(-0.5, -0.5, 0.0), (-0.5, 0.25, 0.0), (0.5, -0.5, 0.0), (0.5, 0.25, 0.0)];
+
+        strip.fill_triangle_strip(&points, color);
+        single.fill_triangle(points[0], points[1], points[2], color);
+        single.fill_triangle(points[1], points[2], points[3], color);
+
+        assert_eq!(strip.buffer, single.buffer);
+        assert_eq!(strip.depth_buffer, single.depth_buffer);
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&strip, 8 * 16 + x), (color, 5));
+        }
+        assert_eq!(pixel_at(&strip, 8 * 16 + 13), BLANK);
+
+        let mut empty = ViewportFactory::test(16, 16, 10);
+        empty.fill_triangle_strip(&points[..2], color);
+        empty.fill_triangle_fan(points[0], &points[1..2], color);
+        assert!(empty.buffer.iter().all(|&pixel| pixel == BLANK.0));
+    }
+
+    #[test]
+    fn draw_quad() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_quad((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.25, 0.0), (-0.5, 0.25, 0.0), color);
+
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&viewport, 4 * 16 + x), (color, 5));
+            assert_eq!(pixel_at(&viewport, 10 * 16 + x), (color, 5));
+        }
+        for y in 4..=10 {
+            assert_eq!(pixel_at(&viewport, y * 16 + 4), (color, 5));
+            assert_eq!(pixel_at(&viewport, y * 16 + 12), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 8), BLANK);
+    }
+
+    #[test]
+    fn flood_fill() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let border = [255, 255, 255, 255];
+        let fill = [255, 0, 0, 255];
+
+        viewport.draw_rectangle((-0.5, -0.5, 0.0), (0.5, 0.25, 0.0), border);
+        viewport.draw_line((-0.25, -0.5, 0.0), (-0.25, 0.25, 0.0), border);
+        viewport.flood_fill((0.0, 0.0, 0.0), fill);
+
+        for y in 5..10 {
+            for x in 7..12 {
+                assert_eq!(pixel_at(&viewport, y * 16 + x), (fill, 0));
+            }
+            assert_eq!(pixel_at(&viewport, y * 16 + 5), BLANK);
+            assert_eq!(pixel_at(&viewport, y * 16 + 6), (border, 5));
+            assert_eq!(pixel_at(&viewport, y * 16 + 12), (border, 5));
+            assert_eq!(pixel_at(&viewport, y * 16 + 13), BLANK);
+        }
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == fill).count(), 25);
+
+        viewport.flood_fill((-1.0, -1.0, 0.0), fill);
+        assert_eq!(pixel_at(&viewport, 0), (fill, 0));
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 5), BLANK);
+    }
+
+    #[test]
+    fn draw_line_clip_z() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_line((-1.0, -1.0, -2.0), (0.5, 0.5, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 6 * 16 + 6), (color, 0));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 5), BLANK);
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 12), (color, 5));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 7);
+
+        viewport.reset_buffer();
+        viewport.draw_line((-1.0, -1.0, 1.5), (0.5, 0.5, 2.0), color);
+        assert_eq!(viewport.dirty_bounds(), None);
+    }
+
+    #[test]
+    fn draw_polyline() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_polyline(&[(-0.5, -0.5, 0.0)], color);
+        assert_eq!(viewport.dirty_bounds(), None);
+
+        viewport.draw_polyline(&[(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0)], color);
+        for i in 4..=12 {
+            assert_eq!(pixel_at(&viewport, 4 * 16 + i), (color, 5));
+            assert_eq!(pixel_at(&viewport, i * 16 + 12), (color, 5));
+        }
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 17);
+    }
+
+    #[test]
+    fn draw_polygon() {
+        let mut polygon = ViewportFactory::test(16, 16, 10);
+        let mut triangle = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let (a, b, c) = ((-0.5, -0.75, 0.2), (0.75, 0.0, -0.4), (-0.25, 0.5, 0.0));
+
+        polygon.draw_polygon(&[a, b, c], color);
+        triangle.draw_triangle(a, b, c, color);
+
+        assert_eq!(polygon.buffer, triangle.buffer);
+        assert_eq!(polygon.depth_buffer, triangle.depth_buffer);
+    }
+
+    #[test]
+    fn draw_line_depth() {
+        let mut viewport = ViewportFactory::test(16, 16, 1000);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_line((-0.5, 0.0, -0.5), (0.5, 0.0, 0.5), color);
+
+        let depths: Vec<usize> = (4..=12).map(|x| pixel_at(&viewport, 8 * 16 + x).1).collect();
+        assert_eq!(depths.first(), Some(&250));
+        assert_eq!(depths.last(), Some(&750));
+        assert!(depths.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn transform() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.push_transform(Transform2D::translate(0.5, -0.25));
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 6 * 16 + 12), (color, 5));
+
+        viewport.push_transform(Transform2D::scale(0.5, 0.5));
+        viewport.draw_point((0.5, 0.5, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 14), (color, 5));
+
+        viewport.pop_transform();
+        viewport.pop_transform();
+        viewport.pop_transform();
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 3);
+    }
+
+    #[test]
+    fn buffer_rgba() {
+        let mut viewport = ViewportFactory::test(8, 4, 10);
+        assert_eq!((viewport.width_px(), viewport.height_px()), (8, 4));
+
+        let color = [255, 128, 64, 32];
+        viewport.draw_point_px((3, 2, 0), color);
+        let bytes = viewport.buffer_rgba();
+        assert_eq!(bytes.len(), 8 * 4 * 4);
+
+        let offset = buffer_index(3, 2, 8) * 4;
+        assert_eq!(bytes[offset..offset + 4], color);
+        assert_eq!(bytes.iter().filter(|&&byte| byte != 0).count(), 4);
+    }
+
+    #[test]
+    fn buffer_bytes() {
+        let mut viewport = ViewportFactory::test(8, 4, 10);
+        viewport.fill_triangle((-1.0, -1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, 1.0, 0.0), [255, 0, 0, 255]);
+        viewport.draw_line((-1.0, 1.0, 0.5), (1.0, -1.0, 0.5), [0, 255, 0, 128]);
+        viewport.draw_point_px((7, 3, 0), [0, 0, 255, 64]);
+
+        let flattened: Vec<u8> = viewport.buffer.iter().flatten().copied().collect();
+        assert_eq!(viewport.buffer_bytes(), flattened.as_slice());
+        assert_eq!(viewport.buffer_rgba(), flattened);
+    }
+
+    #[test]
+    fn set_depth() {
+        let mut viewport = ViewportFactory::test(4, 4, 10);
+        let color = [255, 255, 255, 255];
+        viewport.draw_point_px((0, 0, 0), color);
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 10), (color, 5));
+
+        assert!(matches!(
+            viewport.set_depth(0),
+            Err(ViewportError::InvalidDimensions { depth: 0, .. })
+        ));
+        assert_eq!(viewport.depth(), 10);
+
+        viewport.set_depth(100).unwrap();
+        viewport.reset_buffer();
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 10), (color, 50));
+        assert_eq!(pixel_at(&viewport, 0), BLANK);
+    }
+
+    #[test]
+    fn draw_points() {
+        let mut batch = ViewportFactory::test(16, 16, 10);
+        let mut single = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let points = [(-1.0, -1.0, -1.0), (0.5, 0.75, 0.5), (0.5, 0.75, -0.5)];
+
+        batch.draw_points(&points, color);
+        for position in points {
+            single.draw_point(position, color);
+        }
+
+        assert_eq!(batch.buffer, single.buffer);
+        assert_eq!(batch.depth_buffer, single.depth_buffer);
+        assert_eq!(pixel_at(&batch, 14 * 16 + 12), (color, 7));
+    }
+
+    #[test]
+    fn clip_rect() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.set_clip_rect((0.5, 0.5, 0.0), (-0.5, -0.5, 0.0));
+        viewport.draw_line((-1.0, 0.0, 0.0), (1.0, 0.0, 0.0), color);
+        for x in 0..16 {
+            let expected = if (4..=12).contains(&x) { (color, 5) } else { BLANK };
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), expected);
+        }
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), color);
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 9 * 9);
+        let red = [255, 0, 0, 255];
+        viewport.fill_triangle((-1.0, -1.0, 0.5), (3.0, -1.0, 0.5), (-1.0, 3.0, 0.5), red);
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == red).count(), 9 * 9);
+
+        viewport.clear_clip_rect();
+        viewport.draw_point((-1.0, -1.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 0), (color, 5));
+    }
+
+    #[test]
+    fn draw_grid() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_grid(2, color);
+        for i in 0..16 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + i), (color, 10));
+            assert_eq!(pixel_at(&viewport, i * 16 + 8), (color, 10));
+            assert_eq!(pixel_at(&viewport, i), (color, 10));
+            assert_eq!(pixel_at(&viewport, i * 16 + 15), (color, 10));
+        }
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 4), BLANK);
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 6 * 16 - 9);
+
+        viewport.reset_buffer();
+        viewport.draw_grid(0, color);
+        assert!(viewport.buffer.iter().all(|&pixel| pixel == BLANK.0));
+    }
+
+    #[test]
+    fn draw_arrow() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_arrow((-0.5, 0.0, 0.0), (0.5, 0.0, 0.0), 0.25, color);
+
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 9 * 16 + 10), (color, 5));
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 10), (color, 5));
+        assert_eq!(pixel_at(&viewport, 9 * 16 + 6), BLANK);
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 13), BLANK);
+
+        viewport.reset_buffer();
+        viewport.draw_arrow((0.0, 0.0, 0.0), (0.0, 0.0, 0.0), 0.25, color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 1);
+    }
+
+    #[test]
+    fn draw_lines() {
+        let mut batch = ViewportFactory::test(16, 16, 10);
+        let mut single = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let segments = [
+            ((-1.0, -1.0, -1.0), (0.5, 0.75, 0.5)),
+            ((0.75, -0.5, 0.0), (-0.5, 0.25, 1.0)),
+            ((-0.25, 0.9, 0.2), (-0.25, -0.9, -0.2)),
+        ];
+
+        batch.draw_lines(&segments, color);
+        for (start, end) in segments {
+            single.draw_line(start, end, color);
+        }
+
+        assert_eq!(batch.buffer, single.buffer);
+        assert_eq!(batch.depth_buffer, single.depth_buffer);
+        assert!(batch.buffer.contains(&color));
+    }
+
+    #[test]
+    fn fill_mesh() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let vertices = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+
+        viewport.fill_mesh(&vertices, &[(0, 1, 2), (0, 2, 3)], color).unwrap();
+
+        for y in 5..11 {
+            for x in 5..11 {
+                assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 5));
+            }
+        }
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 8, 16)), BLANK);
+        assert_eq!(pixel_at(&viewport, buffer_index(13, 8, 16)), BLANK);
+    }
+
+    #[test]
+    fn draw_mesh() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let vertices = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+
+        viewport.draw_mesh(&vertices, &[(0, 1, 2), (0, 2, 3)], color).unwrap();
+
+        assert_eq!(pixel_at(&viewport, buffer_index(4, 4, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(12, 12, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(10, 6, 16)), BLANK);
+    }
+
+    #[test]
+    fn mesh_out_of_bounds() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let vertices = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0)];
+
+        let result = viewport.fill_mesh(&vertices, &[(0, 1, 2), (0, 2, 3)], color);
+
+        assert!(matches!(result, Err(ViewportError::IndexOutOfBounds { index: 3, len: 3 })));
+        assert!(viewport.buffer.iter().all(|&color| color == BLANK.0));
+    }
+
+    #[test]
+    fn y_up() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.set_y_up(true);
+        viewport.draw_point((-1.0, -1.0, 0.0), color);
+        viewport.draw_line((0.0, -1.0, 0.0), (0.0, -0.5, 0.0), color);
+        viewport.fill_rectangle((0.5, 0.5, 0.0), (0.75, 1.0, 0.0), color);
+
+        assert_eq!(pixel_at(&viewport, 15 * 16), (color, 5));
+        assert_eq!(pixel_at(&viewport, 0), BLANK);
+        for y in 12..16 {
+            assert_eq!(pixel_at(&viewport, y * 16 + 8), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 12), (color, 5));
+        assert_eq!(pixel_at(&viewport, 3 * 16 + 14), (color, 5));
+        assert_eq!(viewport.get_pixel((-1.0, -1.0, 0.0)), Some((color, 5)));
+
+        viewport.set_y_up(false);
+        viewport.draw_point((-1.0, -1.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 0), (color, 5));
+    }
+
+    #[test]
+    fn draw_line() {
+        let mut viewport = ViewportFactory::test(24, 24, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_line((-0.25, -0.25, 0.0), (0.25, 0.25, 0.0), color);
+
+        for i in 0..7 {
+            assert_eq!(pixel_at(&viewport, 225 + i * 25), (color, 5));
+        }
+    }
+
+    #[test]
+    fn draw_line_aa() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_line_aa((-0.5, -0.5, 0.0), (0.5, 0.0, 0.0), color);
+
+        // Check the ends have full coverage
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 4), (color, 5));
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 12), (color, 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 4), BLANK);
+
+        // Check the pixels between rows share the coverage
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 5), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 5), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 6), (color, 5));
+
+        // Check steep lines
+        viewport.reset_buffer();
+        viewport.draw_line_aa((0.0, 0.5, 0.0), (-0.5, -0.5, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 4), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 4), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, 5 * 16 + 5), ([255, 255, 255, 128], 5));
+    }
+
+    #[test]
+    fn fill_triangle_aa() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_triangle_aa((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (-0.5, 0.5, 0.0), color);
+
+        // Check the interior is solid
+        assert_eq!(pixel_at(&viewport, buffer_index(6, 6, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(5, 9, 16)), (color, 5));
+
+        // Check the straight edges are half covered and the diagonal fades out
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 4, 16)), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(4, 8, 16)), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), ([255, 255, 255, 128], 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(9, 8, 16)).0[3], 0);
+        assert_eq!(pixel_at(&viewport, buffer_index(3, 8, 16)), BLANK);
+        assert_eq!(pixel_at(&viewport, buffer_index(10, 10, 16)), BLANK);
+
+        let covered = viewport.buffer.iter().filter(|pixel| pixel[3] > 0);
+        assert!(covered.clone().all(|pixel| pixel[..3] == color[..3]));
+        assert!(covered.clone().any(|pixel| pixel[3] < 255));
+    }
+
+    #[test]
+    fn draw_thick_line() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_thick_line((-0.5, 0.0, 0.0), (0.5, 0.0, 0.0), 3, color);
+        for y in 7..=9 {
+            assert_eq!(pixel_at(&viewport, y * 16 + 8), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 6 * 16 + 8), BLANK);
+        assert_eq!(pixel_at(&viewport, 10 * 16 + 8), BLANK);
+
+        viewport.reset_buffer();
+        viewport.draw_thick_line((0.0, -0.5, 0.0), (0.0, 0.5, 0.0), 4, color);
+        for x in 7..=10 {
+            assert_eq!(pixel_at(&viewport, 8 * 16 + x), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 6), BLANK);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 11), BLANK);
+
+        // Diagonal runs are stretched to keep the perpendicular width
+        viewport.reset_buffer();
+        viewport.draw_thick_line((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), 3, color);
+        let stamped = (0..16).filter(|y| pixel_at(&viewport, y * 16 + 8) != BLANK).count();
+        assert_eq!(stamped, 4);
+    }
+
+    #[test]
+    fn thin_thick_line() {
+        let color = [255, 255, 255, 255];
+        let mut line = ViewportFactory::test(24, 24, 10);
+        let mut thick = ViewportFactory::test(24, 24, 10);
+
+        line.draw_line((-0.25, -0.75, -0.5), (0.5, 0.25, 0.5), color);
+        thick.draw_thick_line((-0.25, -0.75, -0.5), (0.5, 0.25, 0.5), 1, color);
+        assert_eq!(line.buffer, thick.buffer);
+
+        thick.reset_buffer();
+        thick.draw_thick_line((-0.25, -0.75, -0.5), (0.5, 0.25, 0.5), 0, color);
+        assert!(thick.buffer.iter().all(|&color| color == BLANK.0));
+    }
+
+    #[test]
+    fn draw_bezier() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_quadratic_bezier((-0.5, 0.5, 0.0), (0.0, -0.5, 0.0), (0.5, 0.5, 0.0), color);
+
+        // Check the ends and the peak of the curve
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 4), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 16 + 12), (color, 5));
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 8), BLANK);
+    }
+
+    #[test]
+    fn straight_bezier() {
+        let color = [255, 255, 255, 255];
+        let mut line = ViewportFactory::test(24, 24, 10);
+        let mut quadratic = ViewportFactory::test(24, 24, 10);
+        let mut cubic = ViewportFactory::test(24, 24, 10);
+
+        line.draw_line((-0.75, -0.75, 0.0), (0.75, 0.75, 0.0), color);
+        line.draw_line((-0.75, 0.5, 0.0), (0.75, 0.5, 0.0), color);
+        quadratic.draw_quadratic_bezier((-0.75, -0.75, 0.0), (0.0, 0.0, 0.0), (0.75, 0.75, 0.0), color);
+        quadratic.draw_quadratic_bezier((-0.75, 0.5, 0.0), (0.25, 0.5, 0.0), (0.75, 0.5, 0.0), color);
+        cubic.draw_cubic_bezier((-0.75, -0.75, 0.0), (-0.25, -0.25, 0.0), (0.25, 0.25, 0.0), (0.75, 0.75, 0.0), color);
+        cubic.draw_cubic_bezier((-0.75, 0.5, 0.0), (-0.5, 0.5, 0.0), (0.5, 0.5, 0.0), (0.75, 0.5, 0.0), color);
+
+        assert_eq!(line.buffer, quadratic.buffer);
+        assert_eq!(line.buffer, cubic.buffer);
+    }
+
+    #[test]
+    fn draw_triangle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_triangle(
+            (0.0, -0.25, 0.0),
+            (-0.25, 0.0, 0.0),
+            (0.25, 0.0, 0.0),
+            color,
+        );
+
+        // Check points in each of the lines
+        assert_eq!(pixel_at(&viewport, 119), (color, 5));
+        assert_eq!(pixel_at(&viewport, 135), (color, 5));
+        assert_eq!(pixel_at(&viewport, 121), (color, 5));
+    }
+
+    #[test]
     fn fill_triangle() {
         let mut viewport = ViewportFactory::test(16, 16, 10);
-        let color = &[255, 255, 255, 255];
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_triangle(
+            (0.0, -0.25, 0.0),
+            (-0.25, 0.0, 0.0),
+            (0.25, 0.0, 0.0),
+            color,
+        );
+
+        // Check points in each of the lines
+        assert_eq!(pixel_at(&viewport, 119), (color, 5));
+        assert_eq!(pixel_at(&viewport, 135), (color, 5));
+        assert_eq!(pixel_at(&viewport, 121), (color, 5));
+
+        // Check point inside
+        assert_eq!(pixel_at(&viewport, 120), (color, 5));
+    }
+
+    #[test]
+    fn fill_triangle_scanline() {
+        let color = [255, 255, 255, 255];
+        let triangles = [
+            ((0.0, -0.25, 0.0), (-0.25, 0.0, 0.0), (0.25, 0.0, 0.0)),
+            ((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (-0.5, 0.5, 0.0)),
+            ((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (0.5, -0.5, 0.0)),
+        ];
+        for (a, b, c) in triangles {
+            let mut scanline = ViewportFactory::test(16, 16, 10);
+            let mut bresenham = ViewportFactory::test(16, 16, 10);
+            scanline.fill_triangle_scanline(a, b, c, color);
+            bresenham.fill_triangle(a, b, c, color);
+            assert_eq!(scanline.buffer, bresenham.buffer);
+        }
+
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        viewport.fill_triangle_scanline((-0.5, 0.0, 0.0), (0.5, 0.0, 0.0), (0.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, 132), (color, 5));
+        assert_eq!(pixel_at(&viewport, 139), (color, 5));
+        assert_eq!(pixel_at(&viewport, 116), BLANK);
+    }
+
+    #[test]
+    fn draw_circle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_circle((0.0, 0.0, 0.0), 0.5, color);
+
+        // Check the four extremes of the circle
+        assert_eq!(pixel_at(&viewport, 140), (color, 5));
+        assert_eq!(pixel_at(&viewport, 132), (color, 5));
+        assert_eq!(pixel_at(&viewport, 72), (color, 5));
+        assert_eq!(pixel_at(&viewport, 200), (color, 5));
+        // Check a diagonal point
+        assert_eq!(pixel_at(&viewport, 11 * 16 + 11), (color, 5));
+
+        // Check center is empty
+        assert_eq!(pixel_at(&viewport, 136), BLANK);
+    }
+
+    #[test]
+    fn fill_circle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_circle((0.0, 0.0, 0.0), 0.5, color);
+
+        // Check the four extremes of the circle
+        assert_eq!(pixel_at(&viewport, 140), (color, 5));
+        assert_eq!(pixel_at(&viewport, 132), (color, 5));
+        assert_eq!(pixel_at(&viewport, 72), (color, 5));
+        assert_eq!(pixel_at(&viewport, 200), (color, 5));
+
+        // Check points inside
+        assert_eq!(pixel_at(&viewport, 136), (color, 5));
+        assert_eq!(pixel_at(&viewport, 10 * 16 + 6), (color, 5));
+
+        // Check points outside
+        assert_eq!(pixel_at(&viewport, 13 * 16 + 13), BLANK);
+        assert_eq!(pixel_at(&viewport, 141), BLANK);
+    }
+
+    #[test]
+    fn preserve_aspect() {
+        let mut viewport = ViewportFactory::test(32, 16, 10);
+        let color = [255, 255, 255, 255];
+        let extents = |viewport: &Viewport<u32, _>| {
+            let filled: Vec<(usize, usize)> = (0..viewport.buffer.len())
+                .filter(|&i| viewport.buffer[i] == color)
+                .map(|i| (i % 32, i / 32))
+                .collect();
+            let width = filled.iter().map(|p| p.0).max().unwrap() - filled.iter().map(|p| p.0).min().unwrap();
+            let height = filled.iter().map(|p| p.1).max().unwrap() - filled.iter().map(|p| p.1).min().unwrap();
+            (width, height)
+        };
+
+        viewport.fill_circle((0.0, 0.0, 0.0), 0.5, color);
+        assert_eq!(extents(&viewport), (16, 8));
+
+        viewport.reset_buffer();
+        viewport.set_preserve_aspect(true);
+        viewport.fill_circle((0.0, 0.0, 0.0), 0.5, color);
+        assert_eq!(extents(&viewport), (8, 8));
+
+        viewport.reset_buffer();
+        viewport.draw_ellipse((0.0, 0.0, 0.0), 0.5, 0.5, color);
+        assert_eq!(extents(&viewport), (8, 8));
+    }
+
+    #[test]
+    fn circle_out_of_screen() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_circle((-1.0, -1.0, 0.0), 0.5, color);
+        viewport.draw_circle((1.0, 1.0, 0.0), 0.5, color);
+
+        assert_eq!(pixel_at(&viewport, 0), (color, 5));
+        assert_eq!(pixel_at(&viewport, 255), BLANK);
+        assert_eq!(pixel_at(&viewport, 15 * 16 + 11), (color, 5));
+    }
+
+    #[test]
+    fn draw_ellipse() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_ellipse((0.0, 0.0, 0.0), 0.75, 0.25, color);
+
+        // Check the four extremes of the ellipse
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 14), (color, 5));
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 2), (color, 5));
+        assert_eq!(pixel_at(&viewport, 6 * 16 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 10 * 16 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), BLANK);
+
+        // Check the symmetry around the center
+        for y in 0..16 {
+            for x in 0..16 {
+                let pixel = pixel_at(&viewport, y * 16 + x);
+                assert_eq!(pixel, pixel_at(&viewport, y * 16 + (16 - x) % 16));
+                assert_eq!(pixel, pixel_at(&viewport, ((16 - y) % 16) * 16 + x));
+            }
+        }
+    }
+
+    #[test]
+    fn fill_ellipse() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_ellipse((0.0, 0.0, 0.0), 0.75, 0.25, color);
+
+        // Check points inside
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 7 * 16 + 4), (color, 5));
+        assert_eq!(pixel_at(&viewport, 9 * 16 + 12), (color, 5));
+
+        // Check points outside
+        assert_eq!(pixel_at(&viewport, 6 * 16 + 4), BLANK);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 15), BLANK);
+
+        // Check the symmetry around the center
+        for y in 0..16 {
+            for x in 0..16 {
+                let pixel = pixel_at(&viewport, y * 16 + x);
+                assert_eq!(pixel, pixel_at(&viewport, y * 16 + (16 - x) % 16));
+                assert_eq!(pixel, pixel_at(&viewport, ((16 - y) % 16) * 16 + x));
+            }
+        }
+    }
+
+    #[test]
+    fn ellipse_zero_radius() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_ellipse((0.0, 0.0, 0.0), 0.0, 0.0, color);
+        assert_eq!(pixel_at(&viewport, 8 * 16 + 8), (color, 5));
+
+        viewport.fill_ellipse((0.0, -0.5, 0.0), 0.5, 0.0, color);
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&viewport, 4 * 16 + x), (color, 5));
+        }
+        assert_eq!(pixel_at(&viewport, 3 * 16 + 8), BLANK);
+    }
+
+    #[test]
+    fn draw_rectangle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_rectangle((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), color);
+
+        // Check the corners
+        assert_eq!(pixel_at(&viewport, 68), (color, 5));
+        assert_eq!(pixel_at(&viewport, 76), (color, 5));
+        assert_eq!(pixel_at(&viewport, 196), (color, 5));
+        assert_eq!(pixel_at(&viewport, 204), (color, 5));
+
+        // Check point inside is empty
+        assert_eq!(pixel_at(&viewport, 136), BLANK);
+    }
+
+    #[test]
+    fn fill_rectangle() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.fill_rectangle((0.5, 0.5, 0.0), (-0.5, -0.5, 0.0), color);
+
+        // Check the corners
+        assert_eq!(pixel_at(&viewport, 68), (color, 5));
+        assert_eq!(pixel_at(&viewport, 76), (color, 5));
+        assert_eq!(pixel_at(&viewport, 196), (color, 5));
+        assert_eq!(pixel_at(&viewport, 204), (color, 5));
+
+        // Check point inside
+        assert_eq!(pixel_at(&viewport, 136), (color, 5));
+
+        // Check points outside
+        assert_eq!(pixel_at(&viewport, 67), BLANK);
+        assert_eq!(pixel_at(&viewport, 205), BLANK);
+    }
+
+    #[test]
+    fn fill_polygon() {
+        let mut viewport = ViewportFactory::test(32, 32, 10);
+        let color = [255, 255, 255, 255];
+
+        let pentagon = [
+            (0.0, -0.75, 0.0),
+            (0.75, -0.25, 0.0),
+            (0.5, 0.75, 0.0),
+            (-0.5, 0.75, 0.0),
+            (-0.75, -0.25, 0.0),
+        ];
+        viewport.fill_polygon(&pentagon, color);
+
+        // Check the vertices
+        assert_eq!(pixel_at(&viewport, 4 * 32 + 16), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 32 + 28), (color, 5));
+        assert_eq!(pixel_at(&viewport, 28 * 32 + 24), (color, 5));
+        assert_eq!(pixel_at(&viewport, 28 * 32 + 8), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 32 + 4), (color, 5));
+
+        // Check points in the edges
+        assert_eq!(pixel_at(&viewport, 8 * 32 + 22), (color, 5));
+        assert_eq!(pixel_at(&viewport, 28 * 32 + 16), (color, 5));
+
+        // Check points inside
+        assert_eq!(pixel_at(&viewport, 16 * 32 + 16), (color, 5));
+        assert_eq!(pixel_at(&viewport, 12 * 32 + 16), (color, 5));
+        assert_eq!(pixel_at(&viewport, 26 * 32 + 10), (color, 5));
+
+        // Check points outside
+        assert_eq!(pixel_at(&viewport, 2 * 32 + 2), BLANK);
+        assert_eq!(pixel_at(&viewport, 16 * 32 + 30), BLANK);
+        assert_eq!(pixel_at(&viewport, 29 * 32 + 16), BLANK);
+    }
+
+    #[test]
+    fn fill_polygon_depth() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        let square = [(-1.0, -1.0, -1.0), (0.0, -1.0, 1.0), (0.0, 0.0, 1.0), (-1.0, 0.0, -1.0)];
+        viewport.fill_polygon(&square, color);
+        viewport.fill_polygon(&square[..2], [255, 0, 0, 255]); // will be ignored
+
+        assert_eq!(pixel_at(&viewport, 4 * 16), (color, 0));
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 4), (color, 5));
+        assert_eq!(pixel_at(&viewport, 4 * 16 + 8), (color, 10));
+    }
+
+    #[test]
+    fn reset_buffer() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.draw_point((-1.0, -1.0, -1.0), [255, 255, 255, 255]);
+        assert_eq!(pixel_at(&viewport, 0), (color, 0));
+
+        viewport.reset_buffer();
+        assert_eq!(pixel_at(&viewport, 0), BLANK);
+        assert!(viewport.buffer.iter().all(|&color| color == BLANK.0));
+        assert!(viewport.depth_buffer.iter().all(|&depth| depth == BLANK.1));
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        viewport.fill_triangle((-1.0, -1.0, 0.0), (1.0, -0.5, 0.5), (0.0, 1.0, -0.5), [255, 0, 0, 255]);
+        viewport.draw_point((0.0, 0.0, 1.0), [0, 255, 0, 128]);
+        let (buffer, depth_buffer) = (viewport.buffer.clone(), viewport.depth_buffer.clone());
+
+        let snapshot = viewport.snapshot();
+        viewport.reset_buffer();
+        assert!(viewport.buffer.iter().all(|&color| color == BLANK.0));
+        viewport.restore(&snapshot).unwrap();
+        assert_eq!(viewport.buffer, buffer);
+        assert_eq!(viewport.depth_buffer, depth_buffer);
+        assert_eq!(viewport.dirty_bounds(), Some((0, 0, 15, 15)));
+
+        viewport.resize(8, 8);
+        assert!(matches!(
+            viewport.restore(&snapshot),
+            Err(ViewportError::SnapshotMismatch { width: 16, height: 16, expected_width: 8, expected_height: 8 })
+        ));
+        assert!(viewport.buffer.iter().all(|&color| color == BLANK.0));
+    }
+
+    #[test]
+    fn tint() {
+        let mut viewport = ViewportFactory::test(4, 4, 10);
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (0.0, 0.0, 0.0), [255, 255, 255, 255]);
+        viewport.draw_point_px((3, 3, 2), [0, 0, 255, 255]);
+
+        viewport.tint([255, 0, 0, 255], 0.5);
+        assert_eq!(pixel_at(&viewport, 0), ([255, 128, 128, 255], 5));
+        assert_eq!(pixel_at(&viewport, 15), ([128, 0, 128, 255], 2));
+        assert_eq!(pixel_at(&viewport, 3), BLANK);
+        assert_eq!(viewport.dirty_bounds(), Some((0, 0, 3, 3)));
+
+        viewport.tint([0, 255, 0, 255], 2.0);
+        assert_eq!(pixel_at(&viewport, 0), ([0, 255, 0, 255], 5));
+    }
+
+    #[test]
+    fn invert() {
+        let mut viewport = ViewportFactory::test(4, 4, 10);
+        viewport.draw_point_px((1, 1, 3), [255, 0, 0, 255]);
+        viewport.draw_point_px((2, 1, 3), [10, 20, 30, 40]);
+
+        viewport.invert();
+        assert_eq!(pixel_at(&viewport, 5), ([0, 255, 255, 255], 3));
+        assert_eq!(pixel_at(&viewport, 6), ([245, 235, 225, 40], 3));
+        assert_eq!(pixel_at(&viewport, 0), BLANK);
+
+        viewport.set_background([255, 255, 255, 255]);
+        viewport.reset_buffer();
+        viewport.draw_point_px((1, 1, 3), [255, 0, 0, 255]);
+        viewport.invert();
+        assert_eq!(pixel_at(&viewport, 5), ([0, 255, 255, 255], 3));
+        assert_eq!(pixel_at(&viewport, 0), ([255, 255, 255, 255], 0));
+    }
+
+    #[test]
+    fn for_each_pixel() {
+        let mut viewport = ViewportFactory::test(4, 2, 10);
+        viewport.draw_point_px((1, 1, 3), [255, 0, 0, 255]);
+
+        viewport.for_each_pixel(|(x, _), color| color[1] = x as u8 * 85);
+        for y in 0..2 {
+            assert_eq!(pixel_at(&viewport, buffer_index(0, y, 4)), ([0, 0, 0, 0], 0));
+            assert_eq!(pixel_at(&viewport, buffer_index(2, y, 4)), ([0, 170, 0, 0], 0));
+            assert_eq!(pixel_at(&viewport, buffer_index(3, y, 4)), ([0, 255, 0, 0], 0));
+        }
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 1, 4)), ([255, 85, 0, 255], 3));
+        assert_eq!(viewport.dirty_bounds(), Some((0, 0, 3, 1)));
+    }
+
+    #[test]
+    fn flip_and_rotate() {
+        let mut viewport = ViewportFactory::test(3, 3, 10);
+        let (red, green) = ([255, 0, 0, 255], [0, 255, 0, 255]);
+        viewport.draw_point_px((0, 0, 1), red);
+        viewport.draw_point_px((1, 0, 2), red);
+        viewport.draw_point_px((0, 1, 3), green);
+
+        viewport.flip_horizontal();
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 0, 3)), (red, 1));
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 0, 3)), (red, 2));
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 1, 3)), (green, 3));
+        assert_eq!(pixel_at(&viewport, buffer_index(0, 0, 3)), BLANK);
+
+        viewport.flip_vertical();
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 2, 3)), (red, 1));
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 2, 3)), (red, 2));
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 1, 3)), (green, 3));
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 0, 3)), BLANK);
+
+        viewport.rotate_180();
+        assert_eq!(pixel_at(&viewport, buffer_index(0, 0, 3)), (red, 1));
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 0, 3)), (red, 2));
+        assert_eq!(pixel_at(&viewport, buffer_index(0, 1, 3)), (green, 3));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel != BLANK.0).count(), 3);
+    }
+
+    #[test]
+    fn clear_region() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), color);
+        viewport.render().unwrap();
+
+        viewport.clear_region((0.0, 0.5, 0.0), (-0.5, -0.25, 0.0));
+        for y in 0..16 {
+            for x in 0..16 {
+                let expected = if (4..=8).contains(&x) && (6..=12).contains(&y) { BLANK } else { (color, 5) };
+                assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), expected);
+            }
+        }
+        assert_eq!(viewport.dirty_bounds(), Some((4, 6, 8, 12)));
+
+        viewport.clear_region((2.0, 2.0, 0.0), (3.0, 3.0, 0.0)); // will be ignored
+        viewport.clear_region((0.5, 0.5, 0.0), (3.0, 3.0, 0.0));
+        assert_eq!(pixel_at(&viewport, 255), BLANK);
+        assert_eq!(pixel_at(&viewport, buffer_index(11, 11, 16)), (color, 5));
+    }
+
+    #[test]
+    fn content_bounds() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        assert_eq!(viewport.content_bounds(), None);
 
-        viewport.fill_triangle(
-            (0.0, -0.25, 0.0),
-            (-0.25, 0.0, 0.0),
-            (0.25, 0.0, 0.0),
-            color,
+        viewport.draw_point((-0.75, 0.75, 0.0), [255, 0, 0]);
+        viewport.draw_point((0.5, -0.5, 0.0), [0, 255, 0]);
+        viewport.render().unwrap();
+        assert_eq!(viewport.dirty_bounds(), None);
+        assert_eq!(viewport.content_bounds(), Some((2, 4, 12, 14)));
+
+        viewport.set_background([0, 255, 0, 255]);
+        viewport.reset_buffer();
+        assert_eq!(viewport.content_bounds(), None);
+    }
+
+    #[test]
+    fn draw_rounded_rect() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.draw_rounded_rect((-0.5, 0.5, 0.0), (0.5, -0.5, 0.0), 0.25, color);
+        for (x, y) in [(4, 4), (12, 4), (4, 12), (12, 12)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), BLANK, "({x}, {y})");
+        }
+        for (x, y) in [(8, 4), (8, 12), (4, 8), (12, 8), (5, 4), (11, 12)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 5), "({x}, {y})");
+        }
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), BLANK);
+    }
+
+    #[test]
+    fn fill_rounded_rect() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.fill_rounded_rect((-0.5, 0.5, 0.0), (0.5, -0.5, 0.0), 0.25, color);
+        for (x, y) in [(4, 4), (12, 4), (4, 12), (12, 12)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), BLANK, "({x}, {y})");
+        }
+        for (x, y) in [(8, 4), (8, 12), (4, 8), (12, 8), (8, 8), (5, 5)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 5), "({x}, {y})");
+        }
+
+        // the radius is clamped to half the side, so it's drawn like a circle
+        viewport.reset_buffer();
+        viewport.fill_rounded_rect((-0.5, 0.5, 0.0), (0.5, -0.5, 0.0), 2.0, color);
+        assert_eq!(pixel_at(&viewport, buffer_index(4, 5, 16)), BLANK);
+        assert_eq!(pixel_at(&viewport, buffer_index(5, 5, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (color, 5));
+    }
+
+    #[test]
+    fn draw_spline() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        let points = [(-1.0, 0.06, 0.0), (-0.4, 0.08, 0.0), (0.2, 0.04, 0.0), (1.0, 0.06, 0.0)];
+        viewport.draw_spline(&points, color);
+        for x in 0..16 {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, 8, 16)), (color, 5), "{x}");
+        }
+        assert_eq!(viewport.content_bounds(), Some((0, 8, 15, 8)));
+
+        viewport.reset_buffer();
+        viewport.draw_spline(&points[..3], color);
+        assert_eq!(viewport.content_bounds(), Some((0, 8, 9, 8)));
+    }
+
+    #[test]
+    fn draw_box() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.draw_box((-0.5, -0.5, -0.6), (0.5, 0.5, 0.6), color);
+        // the near and far corners overlap, keeping the depth of the near one
+        for (x, y) in [(4, 4), (12, 4), (4, 12), (12, 12)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 8), "({x}, {y})");
+        }
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 4, 16)), (color, 8));
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), BLANK);
+
+        // the far edges are hidden by the near ones but not the other way around
+        viewport.draw_line((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), [255, 0, 0, 255]);
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 4, 16)), (color, 8));
+        viewport.reset_buffer();
+        viewport.draw_line((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), [255, 0, 0, 255]);
+        viewport.draw_box((-0.5, -0.5, -0.6), (0.5, 0.5, 0.6), color);
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 4, 16)), (color, 8));
+    }
+
+    #[test]
+    fn fill_triangle_textured() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let mut expected = ViewportFactory::test(16, 16, 10);
+        let (a, b, c) = ((-0.5, -0.75, 0.0), (0.75, 0.0, 0.5), (-0.25, 0.75, -0.5));
+        let texture = [255, 0, 0, 255].repeat(4);
+
+        viewport.fill_triangle_textured([(a, (0.0, 0.0)), (b, (1.0, 0.0)), (c, (0.0, 1.0))], &texture, 2, 2);
+        expected.fill_triangle(a, b, c, [255, 0, 0, 255]);
+        for i in 0..16 * 16 {
+            assert_eq!(pixel_at(&viewport, i), pixel_at(&expected, i), "{i}");
+        }
+
+        let checkers = [[255, 255, 255, 255], [0, 0, 0, 0], [0, 0, 255, 255], [0, 0, 0, 0]].concat();
+        viewport.fill_triangle_textured(
+            [((-1.0, -1.0, 1.0), (0.0, 0.0)), ((1.0, -1.0, 1.0), (1.0, 0.0)), ((-1.0, 1.0, 1.0), (0.0, 1.0))],
+            &checkers,
+            2,
+            2,
         );
+        assert_eq!(pixel_at(&viewport, 0), ([255, 255, 255, 255], 10));
+        assert_eq!(pixel_at(&viewport, 15), BLANK); // transparent texel
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 10, 16)), ([0, 0, 255, 255], 10));
+    }
 
-        // Check points in each of the lines
-        assert_eq!(viewport.buffer[119], Pixel { color: *color, depth: 5 });
-        assert_eq!(viewport.buffer[135], Pixel { color: *color, depth: 5 });
-        assert_eq!(viewport.buffer[121], Pixel { color: *color, depth: 5 });
+    #[test]
+    fn cull_mode() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let (a, b, c) = ((-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.0, 0.5, 0.0));
+        let clockwise = |viewport: &mut Viewport<u32, _>| {
+            viewport.fill_triangle(a, b, c, [255, 0, 0, 255]);
+            viewport.draw_triangle(a, b, c, [255, 0, 0, 255]);
+        };
+        let counter_clockwise = |viewport: &mut Viewport<u32, _>| {
+            viewport.fill_triangle(a, c, b, [0, 0, 255, 255]);
+            viewport.draw_triangle(a, c, b, [0, 0, 255, 255]);
+        };
 
-        // Check point inside
-        assert_eq!(viewport.buffer[120], Pixel { color: *color, depth: 5 });
+        for (mode, drawn) in [
+            (CullMode::None, [true, true]),
+            (CullMode::Clockwise, [false, true]),
+            (CullMode::CounterClockwise, [true, false]),
+        ] {
+            viewport.set_cull_mode(mode);
+            viewport.reset_buffer();
+            clockwise(&mut viewport);
+            assert_eq!(viewport.content_bounds().is_some(), drawn[0], "{mode:?}");
+            viewport.reset_buffer();
+            counter_clockwise(&mut viewport);
+            assert_eq!(viewport.content_bounds().is_some(), drawn[1], "{mode:?}");
+        }
+
+        // the winding is flipped along the y axis
+        viewport.set_y_up(true);
+        viewport.reset_buffer();
+        clockwise(&mut viewport);
+        assert!(viewport.content_bounds().is_none());
     }
 
     #[test]
-    fn reset_buffer() {
+    fn render_to() {
+        let mut viewport = ViewportFactory::test(4, 4, 10);
+        viewport.draw_point_px((1, 2, 0), [255, 0, 0, 255]);
+        let (mut main, mut minimap) = (MockRenderer::default(), MockRenderer::default());
+
+        viewport.render_to(&mut [&mut main, &mut minimap]).unwrap();
+        for renderer in [&main, &minimap] {
+            assert_eq!(renderer.render_calls, 1);
+            assert_eq!(renderer.last_frame, viewport.buffer);
+        }
+        assert_eq!(viewport.renderer().render_calls, 0);
+        assert_eq!(viewport.dirty_bounds(), Some((1, 2, 1, 2)));
+    }
+
+    #[test]
+    fn set_premultiplied() {
+        let mut viewport = ViewportFactory::headless(4, 4, 10).unwrap();
+        viewport.draw_point_px((0, 0, 0), [255, 255, 255, 128]);
+        viewport.draw_point_px((1, 0, 0), [255, 255, 255, 255]);
+        viewport.render().unwrap();
+        assert_eq!(viewport.renderer().frame()[0..4], [255, 255, 255, 128]);
+
+        viewport.set_premultiplied(true);
+        viewport.render().unwrap();
+        assert_eq!(viewport.renderer().frame()[0..8], [128, 128, 128, 128, 255, 255, 255, 255]);
+        assert_eq!(viewport.buffer[0], [255, 255, 255, 128]);
+    }
+
+    #[test]
+    fn set_fog() {
+        let mut viewport = ViewportFactory::headless(4, 4, 10).unwrap();
+        let fog = [255, 255, 255, 255];
+        viewport.draw_point_px((0, 0, 9), [0, 0, 0, 255]);
+        viewport.draw_point_px((1, 0, 3), [0, 0, 0, 255]);
+        viewport.draw_point_px((2, 0, 10), [0, 0, 0, 255]);
+        viewport.set_fog(fog, 1.0, -1.0);
+        viewport.render().unwrap();
+
+        let frame = viewport.renderer().frame();
+        let (shallow, deep) = (frame[0], frame[4]);
+        assert!(deep > shallow);
+        assert_eq!(frame[0..4], [26, 26, 26, 255]);
+        assert_eq!(frame[4..8], [179, 179, 179, 255]);
+        assert_eq!(frame[8..12], [0, 0, 0, 255]);
+        assert_eq!(frame[12..16], fog);
+        assert_eq!(viewport.buffer[1], [0, 0, 0, 255]);
+
+        viewport.clear_fog();
+        viewport.render().unwrap();
+        assert_eq!(viewport.renderer().frame()[0..8], [0, 0, 0, 255, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn fill_rectangle_gradient() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let (start, end) = ([255, 0, 0, 255], [0, 0, 255, 255]);
+        viewport.fill_rectangle_gradient((-0.5, -0.5, 0.0), (0.5, 0.5, 0.0), start, end, GradientDir::Vertical);
+
+        for x in 4..=12 {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, 4, 16)), (start, 5));
+            assert_eq!(pixel_at(&viewport, buffer_index(x, 12, 16)), (end, 5));
+            assert_eq!(pixel_at(&viewport, buffer_index(x, 8, 16)), ([128, 0, 128, 255], 5));
+        }
+        assert_eq!(pixel_at(&viewport, buffer_index(3, 8, 16)), BLANK);
+
+        viewport.reset_buffer();
+        viewport.fill_rectangle_gradient((0.5, 0.5, 0.0), (-0.5, -0.5, 0.0), start, end, GradientDir::Horizontal);
+        for y in 4..=12 {
+            assert_eq!(pixel_at(&viewport, buffer_index(4, y, 16)), (start, 5));
+            assert_eq!(pixel_at(&viewport, buffer_index(12, y, 16)), (end, 5));
+        }
+    }
+
+    #[test]
+    fn depth_of() {
+        let mut viewport = ViewportFactory::test(16, 8, 10);
+        assert_eq!(viewport.depth_of(-1.0), 0);
+        assert_eq!(viewport.depth_of(0.0), 5);
+        assert_eq!(viewport.depth_of(1.0f64), 10);
+        viewport.draw_point((0.0, 0.0, 0.6), [255, 255, 255, 255]);
+        assert_eq!(viewport.get_depth((0.0, 0.0, 0.0)), Some(viewport.depth_of(0.6)));
+    }
+
+    #[test]
+    fn pixel_to_normalized() {
+        let mut viewport = ViewportFactory::test(16, 8, 10);
+        assert_eq!(viewport.pixel_to_normalized(0.0, 0.0), (-0.9375, -0.875, 0.0));
+        for y_up in [false, true] {
+            viewport.set_y_up(y_up);
+            for (x, y) in [(0, 0), (15, 7), (8, 3), (5, 6)] {
+                let position = viewport.pixel_to_normalized(x as f64, y as f64);
+                assert_eq!(viewport.to_pixel(position), (x, y, 5));
+            }
+        }
+        assert_eq!(viewport.pixel_to_normalized(0.0, 0.0), (-0.9375, 0.875, 0.0));
+    }
+
+    #[test]
+    fn composite() {
+        let (blue, red) = ([0, 0, 255, 255], [255, 0, 0, 255]);
+        let mut scene = ViewportFactory::test(16, 8, 10);
+        scene.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), blue);
+        let mut ui = ViewportFactory::test(16, 8, 20);
+        ui.draw_point_px((1, 1, 20), red);
+        ui.draw_point_px((2, 1, 4), red);
+        ui.draw_point_px((3, 1, 20), [255, 255, 255, 128]);
+
+        scene.set_blend(BlendMode::AlphaOver);
+        scene.composite(&ui).unwrap();
+        assert_eq!(pixel_at(&scene, buffer_index(1, 1, 16)), (red, 10));
+        assert_eq!(pixel_at(&scene, buffer_index(2, 1, 16)), (blue, 5));
+        assert_eq!(pixel_at(&scene, buffer_index(3, 1, 16)), ([128, 128, 255, 255], 10));
+        assert_eq!(pixel_at(&scene, buffer_index(0, 0, 16)), (blue, 5));
+
+        let other = ViewportFactory::test(8, 8, 10);
+        assert!(matches!(scene.composite(&other), Err(ViewportError::SizeMismatch { width: 8, .. })));
+    }
+
+    #[test]
+    fn fill_sector() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+        viewport.fill_sector((0.0, 0.0, 0.0), 0.5, 0.0, FRAC_PI_2, color);
+        for (x, y) in [(8, 8), (10, 10), (12, 8), (8, 12), (9, 11)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 5));
+        }
+        for (x, y) in [(6, 10), (10, 6), (6, 6), (7, 8), (8, 7), (13, 8)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), BLANK);
+        }
+
+        // wraps around zero to fill the right half
+        viewport.reset_buffer();
+        viewport.fill_sector((0.0, 0.0, 0.0), 0.5, 3.0 * FRAC_PI_2, FRAC_PI_2, color);
+        for (x, y) in [(10, 6), (10, 10), (12, 8), (8, 4)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), (color, 5));
+        }
+        for (x, y) in [(6, 8), (6, 6), (5, 10)] {
+            assert_eq!(pixel_at(&viewport, buffer_index(x, y, 16)), BLANK);
+        }
+
+        viewport.reset_buffer();
+        viewport.set_y_up(true);
+        viewport.fill_sector((0.0, 0.0, 0.0), 0.5, 0.0, FRAC_PI_2, color);
+        assert_eq!(pixel_at(&viewport, buffer_index(10, 5, 16)), (color, 5));
+        assert_eq!(pixel_at(&viewport, buffer_index(10, 9, 16)), BLANK);
+    }
+
+    #[test]
+    fn set_depth_test() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let (red, blue) = ([255, 0, 0, 255], [0, 0, 255, 255]);
+        viewport.set_depth_test(false);
+        viewport.draw_point((0.0, 0.0, 0.8), red);
+        viewport.draw_point((0.0, 0.0, -0.5), blue);
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (blue, 2));
+
+        viewport.fill_triangle((-1.0, -1.0, 0.8), (1.0, -1.0, 0.8), (-1.0, 1.0, 0.8), red);
+        viewport.fill_triangle((-1.0, -1.0, -0.5), (1.0, -1.0, -0.5), (-1.0, 1.0, -0.5), blue);
+        assert_eq!(pixel_at(&viewport, buffer_index(2, 2, 16)), (blue, 2));
+
+        viewport.set_depth_test(true);
+        viewport.draw_point((0.0, 0.0, -0.9), red);
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (blue, 2));
+    }
+
+    #[test]
+    fn render_if_changed() {
+        let mut viewport = ViewportFactory::test(16, 8, 10);
+        let color = [255, 255, 255, 255];
+        assert!(viewport.render_if_changed().unwrap());
+        assert!(!viewport.render_if_changed().unwrap());
+        assert_eq!(viewport.renderer.render_calls, 1);
+
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert!(viewport.render_if_changed().unwrap());
+        assert!(!viewport.render_if_changed().unwrap());
+        assert_eq!(viewport.renderer.render_calls, 2);
+
+        // the same frame drawn again after a reset isn't rendered
+        viewport.reset_buffer();
+        viewport.draw_point((0.0, 0.0, 0.0), color);
+        assert!(!viewport.render_if_changed().unwrap());
+        assert_eq!(viewport.renderer.render_calls, 2);
+
+        viewport.reset_buffer();
+        assert!(viewport.render_if_changed().unwrap());
+        assert_eq!(viewport.renderer.render_calls, 3);
+        assert_eq!(viewport.renderer.last_frame[buffer_index(8, 4, 16)], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn draw_sphere() {
+        let mut viewport = ViewportFactory::test(32, 32, 100);
+        let color = [255, 255, 255, 255];
+        viewport.draw_sphere((0.0, 0.0, 0.0), 0.5, 4, color);
+
+        // the equator is seen from its side as a line over the center row
+        for x in 8..=24 {
+            assert_eq!(viewport.buffer[buffer_index(x, 16, 32)], color);
+        }
+        // where the front and the back cross, the front is kept
+        let (front, back) = (viewport.depth_of(0.5), viewport.depth_of(-0.5));
+        let (center, depth) = pixel_at(&viewport, buffer_index(16, 16, 32));
+        assert_eq!(center, color);
+        assert!(depth.abs_diff(front) <= 1);
+        assert!(depth > back);
+        // the gaps of the grid are kept empty
+        assert_eq!(pixel_at(&viewport, buffer_index(14, 12, 32)), BLANK);
+        assert_eq!(pixel_at(&viewport, buffer_index(16, 4, 32)), BLANK);
+    }
+
+    #[test]
+    fn transparent_pixels_keep_depth() {
+        let mut viewport = ViewportFactory::test(4, 4, 10);
+        viewport.draw_point_px((1, 1, 2), [255, 0, 0, 255]);
+        viewport.draw_point_px((1, 1, 8), [0, 255, 0, 0]);
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 1, 4)), ([255, 0, 0, 255], 2));
+
+        viewport.fill_triangle((-1.0, -1.0, 1.0), (1.0, -1.0, 1.0), (-1.0, 1.0, 1.0), [0, 0, 255, 0]);
+        assert_eq!(pixel_at(&viewport, buffer_index(1, 1, 4)), ([255, 0, 0, 255], 2));
+        assert_eq!(pixel_at(&viewport, 0), BLANK);
+    }
+
+    #[test]
+    fn set_antialiasing() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let fractional =
+            |viewport: &Viewport<u32, _>| viewport.buffer.iter().any(|color| color[3] > 0 && color[3] < 255);
+        let (start, end) = ((-1.0, -1.0, 0.0), (1.0, 0.0, 0.0));
+
+        viewport.draw_line(start, end, [255, 255, 255, 255]);
+        assert!(!fractional(&viewport));
+
+        viewport.reset_buffer();
+        viewport.set_antialiasing(true);
+        viewport.draw_line(start, end, [255, 255, 255, 255]);
+        assert!(fractional(&viewport));
+        let antialiased = viewport.buffer.clone();
+        viewport.reset_buffer();
+        viewport.draw_line_aa(start, end, [255, 255, 255, 255]);
+        assert_eq!(viewport.buffer, antialiased);
+
+        viewport.reset_buffer();
+        viewport.fill_triangle(start, end, (-1.0, 1.0, 0.0), [255, 255, 255, 255]);
+        assert!(fractional(&viewport));
+
+        viewport.reset_buffer();
+        viewport.set_antialiasing(false);
+        viewport.fill_triangle(start, end, (-1.0, 1.0, 0.0), [255, 255, 255, 255]);
+        assert!(!fractional(&viewport));
+    }
+
+    #[test]
+    fn set_polygon_mode() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let mut expected = ViewportFactory::test(16, 16, 10);
+        let (a, b, c) = ((-0.5, -0.75, 0.0), (0.75, 0.0, 0.5), (-0.25, 0.75, -0.5));
+        let color = [255, 255, 255, 255];
+
+        viewport.set_polygon_mode(PolygonMode::Line);
+        viewport.fill_triangle(a, b, c, color);
+        expected.draw_triangle(a, b, c, color);
+        assert_eq!(viewport.buffer, expected.buffer);
+        assert_eq!(viewport.depth_buffer, expected.depth_buffer);
+
+        viewport.reset_buffer();
+        expected.reset_buffer();
+        let square = [(-0.5, -0.5, 0.0), (0.5, -0.5, 0.0), (0.5, 0.5, 0.0), (-0.5, 0.5, 0.0)];
+        viewport.fill_polygon(&square, color);
+        expected.draw_polygon(&square, color);
+        assert_eq!(viewport.buffer, expected.buffer);
+
+        viewport.reset_buffer();
+        viewport.set_polygon_mode(PolygonMode::Point);
+        viewport.fill_triangle(a, b, c, color);
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 3);
+
+        viewport.set_polygon_mode(PolygonMode::Fill);
+        viewport.fill_polygon(&square, color);
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (color, 5));
+    }
+
+    #[test]
+    fn stencil() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let mut triangle = ViewportFactory::test(16, 16, 10);
+        let (a, b, c) = ((-0.5, -0.75, 0.0), (0.75, 0.0, 0.5), (-0.25, 0.75, -0.5));
+        let color = [255, 0, 0, 255];
+
+        viewport.begin_stencil();
+        viewport.fill_triangle(a, b, c, [255, 255, 255, 255]);
+        viewport.end_stencil();
+        assert!(viewport.buffer.iter().all(|&pixel| pixel == BLANK.0));
+        assert_eq!(viewport.dirty_bounds(), None);
+
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), color); // ignores the stencil
+        assert!(viewport.buffer.iter().all(|&pixel| pixel == color));
+
+        viewport.reset_buffer();
+        viewport.set_stencil_test(true);
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), color);
+        triangle.fill_triangle(a, b, c, color);
+        assert_eq!(viewport.buffer, triangle.buffer);
+
+        viewport.set_stencil_test(false);
+        viewport.begin_stencil(); // clears the stencil
+        viewport.end_stencil();
+        viewport.set_stencil_test(true);
+        viewport.reset_buffer();
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), color);
+        assert_eq!(viewport.content_bounds(), None);
+    }
+
+    #[test]
+    fn set_bounds_policy() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point((2.0, 0.0, 0.0), color);
+        viewport.draw_point((-2.0, 0.0, 0.0), color);
+        viewport.draw_point((0.0, -2.0, 0.0), color);
+        assert_eq!(viewport.content_bounds(), None);
+        assert!(viewport.render().is_ok());
+
+        viewport.set_bounds_policy(BoundsPolicy::Clamp);
+        viewport.draw_point((2.0, 0.0, 0.0), color);
+        assert_eq!(pixel_at(&viewport, buffer_index(15, 8, 16)), (color, 5));
+        assert!(viewport.render().is_ok());
+
+        viewport.reset_buffer();
+        viewport.set_bounds_policy(BoundsPolicy::Error);
+        viewport.draw_point((2.0, 0.0, 0.0), color);
+        viewport.draw_point((0.0, 3.0, 0.0), color);
+        assert_eq!(viewport.content_bounds(), None);
+        assert!(matches!(viewport.render(), Err(ViewportError::OutOfBounds { x: 24, y: 8 })));
+        assert_eq!(viewport.renderer().render_calls, 2);
+        assert!(viewport.render().is_ok()); // the error is only reported once
+
+        viewport.draw_point((-2.0, 0.0, 0.0), color);
+        assert_eq!(viewport.content_bounds(), None);
+        assert!(matches!(viewport.render(), Err(ViewportError::OutOfBounds { x: -8, y: 8 })));
+        viewport.draw_point((0.0, -2.0, 0.0), color);
+        assert_eq!(viewport.content_bounds(), None);
+        assert!(matches!(viewport.render(), Err(ViewportError::OutOfBounds { x: 8, y: -8 })));
+
+        viewport.fill_triangle((0.0, 0.0, 0.0), (1.5, 0.0, 0.0), (0.0, 0.5, 0.0), color);
+        assert!(matches!(viewport.render_dirty(), Err(ViewportError::OutOfBounds { .. })));
+        assert_eq!(pixel_at(&viewport, buffer_index(8, 8, 16)), (color, 5));
+    }
+
+    #[test]
+    fn reset_buffer_keeps_allocation() {
         let mut viewport = ViewportFactory::test(16, 16, 10);
-        let color = &[255, 255, 255, 255];
-        viewport.draw_point((-1.0, -1.0, -1.0), &[255, 255, 255, 255]);
-        assert_eq!(viewport.buffer[0], Pixel { color: *color, depth: 0 });
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [255, 255, 255, 255]);
+        let buffer = (viewport.buffer.as_ptr(), viewport.buffer.capacity());
+        let depth_buffer = (viewport.depth_buffer.as_ptr(), viewport.depth_buffer.capacity());
 
         viewport.reset_buffer();
-        assert_eq!(viewport.buffer[0], Pixel::default());
+        assert_eq!((viewport.buffer.as_ptr(), viewport.buffer.capacity()), buffer);
+        assert_eq!((viewport.depth_buffer.as_ptr(), viewport.depth_buffer.capacity()), depth_buffer);
+        assert!(viewport.buffer.iter().all(|&color| color == BLANK.0));
+        assert!(viewport.depth_buffer.iter().all(|&depth| depth == BLANK.1));
     }
 
 	#[test]
@@ -417,14 +5725,246 @@ mod test {
         assert_eq!(viewport.renderer.render_calls, 1);
 	}
 
+	#[test]
+	fn render_frame_clock() {
+		let mut viewport = ViewportFactory::test(16, 16, 10);
+		viewport.render().unwrap();
+		assert!(viewport.frame_clock().is_none());
+
+		viewport.set_frame_clock(true);
+		viewport.render().unwrap();
+		std::thread::sleep(std::time::Duration::from_millis(5));
+		viewport.render().unwrap();
+		let clock = viewport.frame_clock().unwrap();
+		assert!(clock.delta_seconds() >= 0.005);
+		assert!(clock.fps() > 0.0);
+
+		viewport.set_frame_clock(false);
+		assert!(viewport.frame_clock().is_none());
+	}
+
+	#[test]
+	fn render_color_lut() {
+		let mut viewport = ViewportFactory::test(16, 16, 10);
+		let mut invert = [0; 256];
+		for (i, value) in invert.iter_mut().enumerate() {
+			*value = 255 - i as u8;
+		}
+		viewport.set_color_lut(Some([invert; 3]));
+		viewport.draw_point((-1.0, -1.0, -1.0), [255, 0, 55, 128]);
+		viewport.render().unwrap();
+
+		assert_eq!(viewport.renderer.last_frame[0], [0, 255, 200, 128]);
+		assert_eq!(viewport.renderer.last_frame[1], [255, 255, 255, 0]);
+		assert_eq!(viewport.buffer[0], [255, 0, 55, 128]);
+
+		viewport.set_color_lut(None);
+		viewport.render().unwrap();
+		assert_eq!(viewport.renderer.last_frame[0], [255, 0, 55, 128]);
+	}
+
+	#[test]
+	fn render_gamma() {
+		let mut viewport = ViewportFactory::headless(4, 4, 10).unwrap();
+		viewport.draw_point_px((0, 0, 0), [128, 128, 128, 255]);
+		viewport.draw_point_px((1, 0, 0), [0, 255, 64, 100]);
+		viewport.render().unwrap();
+		assert_eq!(viewport.renderer().frame()[0..4], [128, 128, 128, 255]);
+
+		viewport.set_gamma(2.2);
+		viewport.set_gamma(0.0); // will be ignored
+		viewport.render().unwrap();
+		let frame = viewport.renderer().frame();
+		assert_eq!(frame[0..4], [186, 186, 186, 255]);
+		assert_eq!(frame[4..8], [0, 255, 136, 100]);
+		assert_eq!(viewport.buffer[0], [128, 128, 128, 255]);
+
+		let mut invert = [0; 256];
+		for (i, value) in invert.iter_mut().enumerate() {
+			*value = 255 - i as u8;
+		}
+		viewport.set_color_lut(Some([invert; 3]));
+		viewport.render().unwrap();
+		assert_eq!(viewport.renderer().frame()[0..4], [186, 186, 186, 255]);
+		assert_eq!(viewport.renderer().frame()[4..8], [255, 0, 224, 100]);
+
+		viewport.set_gamma(1.0);
+		viewport.set_color_lut(None);
+		viewport.render().unwrap();
+		assert_eq!(viewport.renderer().frame()[0..4], [128, 128, 128, 255]);
+	}
+
+	#[test]
+	fn render_headless() {
+		let mut viewport = ViewportFactory::headless(16, 16, 10).unwrap();
+		assert_eq!(viewport.renderer.dimensions(), (16, 16));
+		viewport.draw_point((-1.0, -1.0, 0.0), [255, 0, 0, 255]);
+		viewport.draw_point((0.0, 0.0, 0.0), [0, 255, 0]);
+		viewport.render().unwrap();
+
+		let frame = viewport.renderer.frame();
+		assert_eq!(frame.len(), 16 * 16 * 4);
+		assert_eq!(frame[0..4], [255, 0, 0, 255]);
+		assert_eq!(frame[4..8], [0, 0, 0, 0]);
+		assert_eq!(frame[136 * 4..137 * 4], [0, 255, 0, 255]);
+
+		viewport.clear_frame().unwrap();
+		assert!(viewport.renderer.frame().chunks_exact(4).all(|pixel| pixel == [0, 0, 0, 255]));
+
+		viewport.resize(8, 4);
+		assert_eq!(viewport.renderer.dimensions(), (8, 4));
+		assert_eq!(viewport.renderer.frame().len(), 8 * 4 * 4);
+	}
+
+    #[test]
+    fn render_scaled() {
+        let mut viewport = ViewportFactory::headless_scaled(2, 2, 10, 2).unwrap();
+        assert_eq!((viewport.width_px(), viewport.height_px()), (4, 4));
+        viewport.draw_point_px((0, 0, 0), [255, 255, 255, 255]);
+        viewport.draw_point_px((1, 0, 0), [255, 0, 0, 255]);
+        viewport.draw_point_px((0, 1, 0), [0, 0, 255, 255]);
+        viewport.draw_point_px((1, 1, 0), [0, 0, 0, 255]);
+        viewport.fill_rectangle((0.0, 0.0, 0.0), (1.0, 1.0, 0.0), [0, 255, 0, 255]);
+        viewport.render().unwrap();
+
+        let frame = viewport.renderer().frame();
+        assert_eq!(frame.len(), 2 * 2 * 4);
+        assert_eq!(frame[0..4], [128, 64, 128, 255]);
+        assert_eq!(frame[4..8], [0, 0, 0, 0]);
+        assert_eq!(frame[12..16], [0, 255, 0, 255]);
+
+        viewport.draw_point_px((2, 0, 0), [255, 255, 255, 255]);
+        viewport.render_dirty().unwrap();
+        assert_eq!(viewport.renderer().frame()[4..8], [64, 64, 64, 64]);
+
+        viewport.resize(3, 1);
+        assert_eq!((viewport.width_px(), viewport.height_px()), (6, 2));
+        assert_eq!(viewport.renderer().dimensions(), (3, 1));
+    }
+
+    #[test]
+    fn render_grayscale() {
+        let mut viewport = ViewportFactory::headless(4, 4, 10).unwrap();
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (0.0, 0.0, 0.0), [128]);
+        viewport.draw_point((0.5, 0.5, 0.0), [255]);
+        viewport.render().unwrap();
+
+        let frame = viewport.renderer().frame();
+        assert_eq!(frame[0..4], [128, 128, 128, 255]);
+        assert_eq!(frame[(2 * 4 + 2) * 4..][..4], [128, 128, 128, 255]);
+        assert_eq!(frame[(3 * 4 + 3) * 4..][..4], [255, 255, 255, 255]);
+        assert_eq!(frame[(3 * 4) * 4..][..4], [0, 0, 0, 0]);
+
+        let mut viewport = ViewportFactory::headless_channels::<1>(4, 4, 10).unwrap();
+        viewport.fill_rectangle((-1.0, -1.0, 0.0), (0.0, 0.0, 0.0), [128]);
+        viewport.draw_point((0.5, 0.5, 0.0), [255]);
+        viewport.render().unwrap();
+        assert_eq!(viewport.renderer().frame().len(), 4 * 4);
+        assert_eq!(viewport.renderer().frame()[0], 128);
+        assert_eq!(viewport.renderer().frame()[2 * 4 + 2], 128);
+        assert_eq!(viewport.renderer().frame()[3 * 4 + 3], 255);
+        assert_eq!(viewport.renderer().frame()[3 * 4], 0);
+    }
+
+	#[test]
+	#[cfg(feature = "png")]
+	fn save_png() {
+		let mut viewport = ViewportFactory::test(16, 8, 10);
+		viewport.draw_point((0.0, 0.0, 0.0), [255, 0, 0, 255]);
+		let path = std::env::temp_dir().join("ferrux_viewport_save_png.png");
+		viewport.save_png(&path).unwrap();
+
+		let image = image::open(&path).unwrap().to_rgba8();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(image.dimensions(), (16, 8));
+		assert_eq!(image.get_pixel(8, 4).0, [255, 0, 0, 255]);
+		assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+	}
+
+	#[test]
+	#[cfg(feature = "image")]
+	fn draw_image() {
+		let mut viewport = ViewportFactory::test(16, 8, 10);
+		let mut image = image::RgbaImage::new(2, 2);
+		image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+		image.put_pixel(1, 1, image::Rgba([0, 0, 255, 128]));
+		viewport.draw_image((0.0, 0.0, 0.0), &image.into(), 0.0);
+
+		assert_eq!(pixel_at(&viewport, buffer_index(8, 4, 16)), ([255, 0, 0, 255], 5));
+		assert_eq!(pixel_at(&viewport, buffer_index(9, 4, 16)), BLANK);
+		assert_eq!(pixel_at(&viewport, buffer_index(9, 5, 16)), ([0, 0, 255, 128], 5));
+
+		let output = viewport.to_image();
+		assert_eq!(output.dimensions(), (16, 8));
+		assert_eq!(output.get_pixel(8, 4).0, [255, 0, 0, 255]);
+		assert_eq!(output.get_pixel(9, 5).0, [0, 0, 255, 128]);
+		assert_eq!(output.get_pixel(0, 0).0, [0, 0, 0, 0]);
+	}
+
 	#[test]
 	fn clear() {
 		let mut viewport = ViewportFactory::test(16, 16, 10);
         assert_eq!(viewport.renderer.clear_calls, 0);
 		viewport.clear_frame().unwrap();
         assert_eq!(viewport.renderer.clear_calls, 1);
+        assert_eq!(viewport.renderer.clear_color, [0, 0, 0, 255]);
 	}
 
+    #[test]
+    fn background() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let background = [255, 255, 255, 255];
+        viewport.set_background(background);
+        viewport.draw_point((-1.0, -1.0, -1.0), [255, 0, 0, 255]);
+        viewport.clear_frame().unwrap();
+        assert_eq!(viewport.renderer.clear_color, background);
+
+        viewport.set_background([0, 0, 255, 128]);
+        viewport.clear_frame().unwrap();
+        assert_eq!(viewport.renderer.clear_color, [0, 0, 255, 255]);
+        viewport.set_background(background);
+
+        viewport.reset_buffer();
+        assert!(viewport.buffer.iter().all(|&color| color == background));
+        assert!(viewport.depth_buffer.iter().all(|&depth| depth == 0));
+
+        viewport.clear_frame().unwrap();
+        assert_eq!(viewport.renderer.clear_color, background);
+    }
+
+    #[test]
+    fn resize_preserving() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+        let color = [255, 255, 255, 255];
+
+        viewport.draw_point_px((5, 3, 4), color);
+        viewport.draw_point_px((15, 15, 4), color);
+        viewport.resize_preserving(32, 24);
+        assert_eq!(viewport.buffer.len(), 32 * 24);
+        assert_eq!(viewport.renderer.size, (32, 24));
+        assert_eq!(pixel_at(&viewport, buffer_index(5, 3, 32)), (color, 4));
+        assert_eq!(pixel_at(&viewport, buffer_index(15, 15, 32)), (color, 4));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 2);
+
+        viewport.resize_preserving(8, 8);
+        assert_eq!(pixel_at(&viewport, buffer_index(5, 3, 8)), (color, 4));
+        assert_eq!(viewport.buffer.iter().filter(|&&pixel| pixel == color).count(), 1);
+    }
+
+    #[test]
+    fn handle_event() {
+        let mut viewport = ViewportFactory::test(16, 16, 10);
+
+        viewport.handle_event(&WindowEvent::Resized(PhysicalSize::new(32, 24)));
+        assert_eq!(viewport.buffer.len(), 32 * 24);
+        assert_eq!((viewport.width(), viewport.height()), (32, 24));
+        assert_eq!(viewport.renderer.size, (32, 24));
+
+        viewport.handle_event(&WindowEvent::Resized(PhysicalSize::new(0, 0)));
+        viewport.handle_event(&WindowEvent::CloseRequested);
+        assert_eq!(viewport.buffer.len(), 32 * 24);
+    }
+
 	#[test]
 	fn resize_buffer() {
 		let mut viewport = ViewportFactory::test(16, 16, 10);
@@ -434,9 +5974,17 @@ mod test {
 		assert_eq!(viewport.renderer.size, (32, 32));
 	}
 
-    #[test]
-    #[should_panic]
-    fn wrong_color() {
-        ViewportFactory::test(640, 480, 10).draw_point((0.0, 0.0, 0.0), &[0, 0, 0]);
-    }
+	#[test]
+	fn resize_to_empty() {
+		let mut viewport = ViewportFactory::test(16, 16, 10);
+		viewport.resize(0, 8);
+		viewport.resize(8, 0);
+		viewport.resize_preserving(0, 0);
+		assert_eq!(viewport.buffer.len(), 16 * 16);
+		assert_eq!((viewport.width(), viewport.height()), (16, 16));
+
+		viewport.fill_rectangle((-1.0, -1.0, 0.0), (1.0, 1.0, 0.0), [255, 255, 255, 255]);
+		assert!(viewport.render_dirty().is_ok());
+	}
+
 }
@@ -2,11 +2,15 @@ use winit::window::Window;
 use crate::error::ViewportError;
 use crate::{viewport::Viewport, render};
 
-use super::WinitViewport;
+use super::{HeadlessViewport, WinitViewport};
+#[cfg(feature = "web")]
+use super::WebViewport;
 
 /// Factory to build the viewports for the different window tools, currently it offers the following:
 /// 
 /// * `winit` to use with the [winit] crate
+/// * `headless` to render into memory without any window
+/// * `web` to render into a canvas of the browser, with the `web` feature
 /// 
 pub struct ViewportFactory;
 
@@ -18,7 +22,7 @@ impl ViewportFactory {
 	/// * `window`, reference to the winit Window to draw on.
 	/// 
 	/// # Error
-	/// If no graphics adapter is found
+	/// If no graphics adapter is found or any dimension of the window or the depth is zero
 	/// 
 	/// # Example
 	/// The [`Viewport`] requires a [`Window`] of [`winit`], which will need itself an [`EventLoop`] reference.
@@ -35,14 +39,181 @@ impl ViewportFactory {
 	/// [`Window`]: winit::window::Window
 	///
 	pub fn winit(window: &Window, depth: u32) -> Result<WinitViewport<u32>, ViewportError> {
-		let renderer = render::WinitRenderer::new(window)?;
 		let size = window.inner_size();
+		validate_dimensions(size.width, size.height, depth)?;
+		let renderer = render::WinitRenderer::new(window)?;
 		Ok(Viewport::new(size.width, size.height, depth, renderer))
 	}
 
+	/// Returns a [Viewport] to render the content drawn into a [winit] with supersampling. The buffer is
+	/// allocated at `scale` times the resolution of the window, so all the drawing happens at a higher resolution,
+	/// and it's downsampled averaging the pixels when rendering to reduce the aliasing.
+	/// 
+	/// # Arguments
+	/// * `window`, reference to the winit Window to draw on.
+	/// * `depth`, depth to assume in the `z` axis calculations.
+	/// * `scale`, number of pixels of the buffer per pixel of the window in each axis.
+	/// 
+	/// # Error
+	/// If no graphics adapter is found, any dimension of the window or the depth is zero, or the scale is zero
+	/// 
+	/// # Example
+	/// ```no_run
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let event_loop = winit::event_loop::EventLoop::new();
+	/// let window = winit::window::Window::new(&event_loop)?;
+	/// let viewport = ferrux_viewport::viewport::ViewportFactory::winit_scaled(&window, 100, 2)?;
+	/// # Ok(())}
+	/// ``` 
+	///
+	pub fn winit_scaled(window: &Window, depth: u32, scale: u32) -> Result<WinitViewport<u32>, ViewportError> {
+		let size = window.inner_size();
+		validate_dimensions(size.width, size.height, depth)?;
+		validate_scale(scale)?;
+		let renderer = render::WinitRenderer::new(window)?;
+		Ok(Viewport::new_scaled(size.width, size.height, depth, scale as usize, renderer))
+	}
+
+	/// Returns a [Viewport] rendering into memory, without the need of any window.
+	/// The last rendered frame can be read from the renderer as RGBA bytes.
+	/// 
+	/// # Arguments
+	/// * `width`, width in pixels of the frame.
+	/// * `height`, height in pixels of the frame.
+	/// * `depth`, depth to assume in the `z` axis calculations.
+	/// 
+	/// # Error
+	/// If any of the dimensions is zero
+	/// 
+	/// # Example
+	/// ```
+	/// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless(640, 480, 100).unwrap();
+	/// viewport.draw_point((0.0, 0.0, 0.0), [255, 255, 255, 255]);
+	/// viewport.render().unwrap();
+	/// assert_eq!(viewport.renderer().frame()[(240 * 640 + 320) * 4..][..4], [255, 255, 255, 255]);
+	/// ```
+	///
+	pub fn headless(width: u32, height: u32, depth: u32) -> Result<HeadlessViewport<u32>, ViewportError> {
+		validate_dimensions(width, height, depth)?;
+		Ok(Viewport::new(width, height, depth, render::MemoryRenderer::new(width, height)))
+	}
+
+	/// Returns a [Viewport] rendering into memory with supersampling, see [`ViewportFactory::winit_scaled`].
+	/// 
+	/// # Arguments
+	/// * `width`, width in pixels of the frame.
+	/// * `height`, height in pixels of the frame.
+	/// * `depth`, depth to assume in the `z` axis calculations.
+	/// * `scale`, number of pixels of the buffer per pixel of the frame in each axis.
+	/// 
+	/// # Error
+	/// If any of the dimensions or the scale is zero
+	/// 
+	/// # Example
+	/// ```
+	/// let mut viewport = ferrux_viewport::viewport::ViewportFactory::headless_scaled(640, 480, 100, 2).unwrap();
+	/// assert_eq!((viewport.width(), viewport.height()), (1280, 960));
+	/// viewport.render().unwrap();
+	/// assert_eq!(viewport.renderer().dimensions(), (640, 480));
+	/// ```
+	///
+	pub fn headless_scaled(width: u32, height: u32, depth: u32, scale: u32) -> Result<HeadlessViewport<u32>, ViewportError> {
+		validate_dimensions(width, height, depth)?;
+		validate_scale(scale)?;
+		Ok(Viewport::new_scaled(width, height, depth, scale as usize, render::MemoryRenderer::new(width, height)))
+	}
+
+	/// Returns a [Viewport] rendering into memory frames of `N` channels, see [`render::MemoryRenderer`].
+	/// The buffer is still RGBA, the colors are only packed into the channels of the frame on render.
+	/// 
+	/// # Arguments
+	/// * `width`, width in pixels of the frame.
+	/// * `height`, height in pixels of the frame.
+	/// * `depth`, depth to assume in the `z` axis calculations.
+	/// 
+	/// # Error
+	/// If any of the dimensions is zero
+	/// 
+	/// # Example
+	/// ```
+	/// use ferrux_viewport::viewport::ViewportFactory;
+	///
+	/// let mut viewport = ViewportFactory::headless_channels::<1>(640, 480, 100).unwrap();
+	/// viewport.draw_point((0.0, 0.0, 0.0), [200]);
+	/// viewport.render().unwrap();
+	/// assert_eq!(viewport.renderer().frame()[240 * 640 + 320], 200);
+	/// ```
+	///
+	pub fn headless_channels<const N: usize>(
+		width: u32,
+		height: u32,
+		depth: u32,
+	) -> Result<HeadlessViewport<u32, N>, ViewportError> {
+		validate_dimensions(width, height, depth)?;
+		Ok(Viewport::new(width, height, depth, render::MemoryRenderer::new(width, height)))
+	}
+
+	/// Returns a [Viewport] to render the content drawn into a canvas of the browser, using its 2D context.
+	/// The size of the viewport is the one of the canvas.
+	/// 
+	/// # Arguments
+	/// * `canvas`, canvas element to draw on.
+	/// * `depth`, depth to assume in the `z` axis calculations.
+	/// 
+	/// # Error
+	/// If the 2D context of the canvas can't be obtained or any dimension of the canvas or the depth is zero
+	/// 
+	/// # Example
+	/// ```ignore
+	/// use wasm_bindgen::JsCast;
+	///
+	/// let canvas = web_sys::window().unwrap().document().unwrap().get_element_by_id("canvas").unwrap();
+	/// let canvas = canvas.dyn_into::<web_sys::HtmlCanvasElement>().unwrap();
+	/// let viewport = ferrux_viewport::viewport::ViewportFactory::web(canvas, 100)?;
+	/// ```
+	///
+	#[cfg(feature = "web")]
+	pub fn web(canvas: web_sys::HtmlCanvasElement, depth: u32) -> Result<WebViewport<u32>, ViewportError> {
+		validate_dimensions(canvas.width(), canvas.height(), depth)?;
+		let (width, height) = (canvas.width(), canvas.height());
+		let renderer = render::WebRenderer::new(canvas)?;
+		Ok(Viewport::new(width, height, depth, renderer))
+	}
+
 	#[cfg(test)]
 	pub fn test(width: u32, height: u32, depth: u32) -> Viewport<u32, render::mock::MockRenderer> {
 		Viewport::new(width, height, depth, render::mock::MockRenderer::default())
 	}
 
-}
\ No newline at end of file
+}
+
+/// Checks that all the dimensions of a viewport are greater than zero
+fn validate_dimensions(width: u32, height: u32, depth: u32) -> Result<(), ViewportError> {
+	if width == 0 || height == 0 || depth == 0 {
+		return Err(ViewportError::InvalidDimensions { width, height, depth });
+	}
+	Ok(())
+}
+
+/// Checks that the render scale is greater than zero
+fn validate_scale(scale: u32) -> Result<(), ViewportError> {
+	if scale == 0 {
+		return Err(ViewportError::InvalidScale { scale });
+	}
+	Ok(())
+}
+
+#[test]
+fn headless_invalid_dimensions() {
+	assert!(matches!(ViewportFactory::headless(640, 480, 0), Err(ViewportError::InvalidDimensions { width: 640, height: 480, depth: 0 })));
+	assert!(matches!(ViewportFactory::headless(0, 480, 100), Err(ViewportError::InvalidDimensions { .. })));
+	assert!(matches!(ViewportFactory::headless(640, 0, 100), Err(ViewportError::InvalidDimensions { .. })));
+	assert!(ViewportFactory::headless(1, 1, 1).is_ok());
+}
+
+#[test]
+fn headless_invalid_scale() {
+	assert!(matches!(ViewportFactory::headless_scaled(640, 480, 100, 0), Err(ViewportError::InvalidScale { scale: 0 })));
+	assert!(matches!(ViewportFactory::headless_scaled(0, 480, 100, 2), Err(ViewportError::InvalidDimensions { .. })));
+	assert!(ViewportFactory::headless_scaled(1, 1, 1, 1).is_ok());
+}